@@ -1,6 +1,9 @@
-use clap::{Parser, Subcommand};
+use clap::{Parser, Subcommand, ValueEnum};
+use clap_complete::engine::ArgValueCandidates;
 use clap_complete::Shell;
 
+use crate::complete::{injected_packages, venv_names, venv_scripts};
+
 pub const fn get_styles() -> clap::builder::Styles {
     clap::builder::Styles::styled()
         .usage(
@@ -43,12 +46,36 @@ pub trait Process {
     async fn process(self) -> anyhow::Result<i32>;
 }
 
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default, ValueEnum)]
+pub enum OutputFormat {
+    #[default]
+    Text,
+    Json,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Parser)]
 #[clap(version, styles=get_styles())]
 pub struct Args {
     #[arg(long = "generate", value_enum)]
     pub generator: Option<Shell>,
 
+    #[arg(
+        long,
+        value_enum,
+        global = true,
+        default_value_t,
+        help = "Output format; with `json`, every command prints a structured result to stdout"
+    )]
+    pub output: OutputFormat,
+
+    #[arg(
+        long,
+        global = true,
+        env = "UVENV_OFFLINE",
+        help = "Don't access the network, use cached package data instead"
+    )]
+    pub offline: bool,
+
     #[clap(subcommand)]
     pub cmd: Commands,
 }
@@ -64,11 +91,11 @@ const PYTHON_HELP_TEXT: &str =
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Parser)]
 pub struct SetupOptions {
-    #[clap(long, help = "Don't update $PATH in .bashrc")]
+    #[clap(long, help = "Don't update $PATH in your shell's rc file")]
     pub skip_ensurepath: bool,
-    #[clap(long, help = "Don't enable completions via .bashrc")]
+    #[clap(long, help = "Don't enable completions via your shell's rc file")]
     pub skip_completions: bool,
-    #[clap(long, help = "Don't enable `uvenv activate` via .bashrc")]
+    #[clap(long, help = "Don't enable `uvenv activate` via your shell's rc file")]
     pub skip_activate: bool,
     #[clap(
         short,
@@ -76,6 +103,47 @@ pub struct SetupOptions {
         help = "Setup features without checking previous installation."
     )]
     pub force: bool,
+    #[clap(
+        long,
+        help = "Remove everything `uvenv setup` added to your shell's rc file.",
+        conflicts_with_all = ["skip_ensurepath", "skip_completions", "skip_activate", "force"]
+    )]
+    pub undo: bool,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, ValueEnum)]
+pub enum ListFormat {
+    Table,
+    Csv,
+    Tsv,
+    Json,
+    Yaml,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, ValueEnum)]
+pub enum ListColumn {
+    Name,
+    Installed,
+    Available,
+    Python,
+    Scripts,
+    Injected,
+    Size,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default, ValueEnum)]
+pub enum ListSort {
+    #[default]
+    Name,
+    Python,
+    InstalledDate,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default, ValueEnum)]
+pub enum SbomFormat {
+    #[default]
+    CyclonedxJson,
+    SpdxJson,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Parser)]
@@ -87,17 +155,39 @@ pub struct ListOptions {
     #[clap(short, long, help = "Output in JSON format", conflicts_with_all = ["verbose"])]
     pub json: bool,
 
-    #[clap(long, help = "Don't check for updates", conflicts_with_all = ["show_prereleases", "ignore_constraints"])]
+    #[clap(long, help = "Don't check for updates", conflicts_with_all = ["show_prereleases", "ignore_constraints", "check_yanked"])]
     pub skip_updates: bool,
     #[clap(long, help = "Show prerelease updates", conflicts_with_all = ["skip_updates"])]
     pub show_prereleases: bool,
     #[clap(long, help="Ignore version constraints when checking updates", conflicts_with_all = ["skip_updates"])]
     pub ignore_constraints: bool,
+    #[clap(
+        long,
+        help = "Also flag yanked releases among the installed packages (looks up every dependency)"
+    )]
+    pub check_yanked: bool,
     #[clap(
         long,
         help = "List only packages installed wwith a specific version of Python"
     )]
     pub python: Option<String>,
+
+    #[clap(long, value_enum, help = "Output format (instead of the default blocks)", conflicts_with_all = ["short", "verbose", "json"])]
+    pub format: Option<ListFormat>,
+    #[clap(
+        long,
+        value_enum,
+        value_delimiter = ',',
+        requires = "format",
+        help = "Columns to show with --format, e.g. `name,installed,size`"
+    )]
+    pub columns: Vec<ListColumn>,
+    #[clap(long, value_enum, default_value_t, help = "Order of the packages")]
+    pub sort: ListSort,
+    #[clap(long, help = "Show the dependency tree of each package (see `uvenv tree`)", conflicts_with_all = ["short", "verbose", "json", "format"])]
+    pub tree: bool,
+
+    #[clap(add = ArgValueCandidates::new(venv_names))]
     pub venv_names: Vec<String>,
 }
 
@@ -133,11 +223,19 @@ pub struct CreateOptions {
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Parser)]
 pub struct ActivateOptions {
+    #[clap(add = ArgValueCandidates::new(venv_names))]
+    pub venv_name: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Parser)]
+pub struct ShellOptions {
+    #[clap(add = ArgValueCandidates::new(venv_names))]
     pub venv_name: String,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Parser)]
 pub struct UpgradeOptions {
+    #[clap(add = ArgValueCandidates::new(venv_names))]
     pub package_name: String,
     #[clap(short = 'f', long, help = "Ignore previous version constraint")]
     pub force: bool,
@@ -149,6 +247,7 @@ pub struct UpgradeOptions {
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Parser)]
 pub struct UninstallOptions {
+    #[clap(add = ArgValueCandidates::new(venv_names))]
     pub package_name: String,
     #[clap(
         short = 'f',
@@ -167,11 +266,13 @@ pub struct UninstallAllOptions {
     )]
     pub force: bool,
 
+    #[clap(add = ArgValueCandidates::new(venv_names))]
     pub venv_names: Vec<String>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Parser)]
 pub struct ReinstallOptions {
+    #[clap(add = ArgValueCandidates::new(venv_names))]
     pub package: String,
     #[clap(long, help = PYTHON_HELP_TEXT)]
     pub python: Option<String>,
@@ -204,6 +305,7 @@ pub struct ReinstallAllOptions {
     #[clap(long, short, help = "(Re)install as editable")]
     pub editable: bool,
 
+    #[clap(add = ArgValueCandidates::new(venv_names))]
     pub venv_names: Vec<String>,
 }
 
@@ -216,6 +318,7 @@ pub struct UpgradeAllOptions {
     #[clap(long, help = "Run without `uv` cache")]
     pub no_cache: bool,
 
+    #[clap(add = ArgValueCandidates::new(venv_names))]
     pub venv_names: Vec<String>,
 }
 
@@ -226,8 +329,16 @@ pub struct RunOptions {
     pub no_cache: bool,
     #[clap(long, help = PYTHON_HELP_TEXT)]
     pub python: Option<String>,
-    #[clap(long, help = "Don't remove the temporary venv when done running")]
+    #[clap(
+        long,
+        help = "Print the path of the venv that is used (run venvs are now always kept and reused for a while, see `uvenv cache prune-runs`)"
+    )]
     pub keep: bool,
+    #[clap(
+        long,
+        help = "Rebuild the cached venv for this run, even if it's still fresh"
+    )]
+    pub refresh: bool,
     #[clap(long, short, help = "Include extra dependencies")]
     pub with: Vec<String>,
     #[clap(
@@ -240,22 +351,39 @@ pub struct RunOptions {
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Parser)]
 pub struct RunuvOptions {
+    #[clap(add = ArgValueCandidates::new(venv_names))]
     pub venv: String,
     pub uv_args: Vec<String>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Parser)]
 pub struct RunpipOptions {
+    #[clap(add = ArgValueCandidates::new(venv_names))]
     pub venv: String,
     pub pip_args: Vec<String>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Parser)]
 pub struct RunpythonOptions {
+    #[clap(add = ArgValueCandidates::new(venv_names))]
     pub venv: String,
     pub python_args: Vec<String>,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Parser)]
+pub struct ExecOptions {
+    #[clap(add = ArgValueCandidates::new(venv_names))]
+    pub venv: String,
+    #[clap(
+        required = true,
+        trailing_var_arg = true,
+        allow_hyphen_values = true,
+        help = "Command to run, e.g. `uvenv exec httpie -- pytest -x`",
+        add = ArgValueCandidates::new(venv_scripts)
+    )]
+    pub command: Vec<String>,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Parser)]
 pub struct EnsurepathOptions {
     #[clap(long, short, help = "Force update")]
@@ -264,6 +392,7 @@ pub struct EnsurepathOptions {
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Parser)]
 pub struct InjectOptions {
+    #[clap(add = ArgValueCandidates::new(venv_names))]
     pub into: String,
     pub package_specs: Vec<String>,
 
@@ -272,7 +401,9 @@ pub struct InjectOptions {
 }
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Parser)]
 pub struct UnInjectOptions {
+    #[clap(add = ArgValueCandidates::new(venv_names))]
     pub outof: String,
+    #[clap(add = ArgValueCandidates::new(injected_packages))]
     pub package_specs: Vec<String>,
 }
 
@@ -280,7 +411,7 @@ pub struct UnInjectOptions {
 pub struct CheckOptions {
     #[clap(long, help = "Don't check if scripts are installed correctly.")]
     pub skip_scripts: bool,
-    #[clap(long, help = "Don't check for updates", conflicts_with_all = ["show_prereleases", "ignore_constraints"])]
+    #[clap(long, help = "Don't check for updates (or yanked releases)", conflicts_with_all = ["show_prereleases", "ignore_constraints"])]
     pub skip_updates: bool,
     #[clap(long, help = "Show prerelease updates", conflicts_with_all = ["skip_updates"])]
     pub show_prereleases: bool,
@@ -290,28 +421,134 @@ pub struct CheckOptions {
     #[clap(long, short, help = "Output as JSON")]
     pub json: bool,
 
+    #[clap(long, help = "Try to resolve the issues that were found")]
+    pub fix: bool,
+    #[clap(long, help = "Only show what --fix would do", requires = "fix")]
+    pub dry_run: bool,
+    #[clap(long, help = "Also upgrade outdated packages with --fix", requires = "fix", conflicts_with_all = ["skip_updates"])]
+    pub upgrade: bool,
+
+    #[clap(add = ArgValueCandidates::new(venv_names))]
+    pub venv_names: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Parser)]
+pub struct TreeOptions {
+    #[clap(long, short, help = "Output as JSON")]
+    pub json: bool,
+
+    #[clap(add = ArgValueCandidates::new(venv_names))]
+    pub venv_names: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Parser)]
+#[clap(args_conflicts_with_subcommands = true)]
+pub struct AuditOptions {
+    #[clap(subcommand)]
+    pub command: Option<AuditCommands>,
+
+    #[clap(long, short, help = "Output as JSON")]
+    pub json: bool,
+
+    #[clap(add = ArgValueCandidates::new(venv_names))]
+    pub venv_names: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Parser)]
+pub struct AuditUpdateDbOptions;
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Subcommand)]
+pub enum AuditCommands {
+    #[clap(about = "Download a fresh snapshot of the OSV advisory database for PyPI.")]
+    UpdateDb(AuditUpdateDbOptions),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Parser)]
+pub struct LicensesOptions {
+    #[clap(long, short, help = "Output as JSON")]
+    pub json: bool,
+    #[clap(
+        long,
+        value_delimiter = ',',
+        help = "Flag these licenses, in addition to the denylist in config.toml (e.g. `--deny AGPL,GPL-3.0`)"
+    )]
+    pub deny: Vec<String>,
+
+    #[clap(add = ArgValueCandidates::new(venv_names))]
+    pub venv_names: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Parser)]
+pub struct SbomOptions {
+    #[clap(long, value_enum, default_value_t, help = "SBOM format")]
+    pub format: SbomFormat,
+
+    #[clap(add = ArgValueCandidates::new(venv_names))]
+    pub venv_names: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Parser)]
+pub struct WhichOptions {
+    #[clap(long, short, help = "Output as JSON")]
+    pub json: bool,
+
+    #[clap(help = "Name of the command, e.g. `http`")]
+    pub command: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Parser)]
+pub struct InfoOptions {
+    #[clap(long, short, help = "Output as JSON")]
+    pub json: bool,
+
+    #[clap(add = ArgValueCandidates::new(venv_names))]
+    pub venv_name: String,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Parser)]
+pub struct GcOptions {
+    #[clap(long, help = "Only show what would be removed")]
+    pub dry_run: bool,
+    #[clap(
+        long,
+        help = "Don't remove unused entries from uv's cache (`uv cache prune`)"
+    )]
+    pub keep_uv_cache: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Parser)]
+pub struct DuOptions {
+    #[clap(long, short, help = "Output as JSON")]
+    pub json: bool,
+
+    #[clap(add = ArgValueCandidates::new(venv_names))]
     pub venv_names: Vec<String>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Parser)]
 pub struct CompletionsOptions {
-    #[clap(long, short, help = "Add to ~/.bashrc")]
+    #[clap(long, short, help = "Add to your shell's rc file (e.g. ~/.bashrc)")]
     pub install: bool,
-    // todo: support others than bash
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Parser)]
 pub enum Commands {
-    #[clap(about = "Setup additional (bash-specific) functionality.")]
+    #[clap(about = "Setup additional shell-specific functionality (bash, zsh, fish).")]
     Setup(SetupOptions),
     #[clap(about = "List packages and apps installed with uvenv.")]
     List(ListOptions),
+    #[clap(about = "Show the dependency tree of installed packages (💉 = injected).")]
+    Tree(TreeOptions),
+    #[clap(about = "Show everything about one installed package.")]
+    Info(InfoOptions),
     #[clap(about = "Install a package (by pip name).")]
     Install(InstallOptions),
     #[clap(about = "Create a new (empty) virtualenv")]
     Create(CreateOptions),
-    #[clap(about = "Activate a uvenv-managed virtualenv (bash only)")]
+    #[clap(about = "Activate a uvenv-managed virtualenv (bash, zsh, fish)")]
     Activate(ActivateOptions),
+    #[clap(about = "Start a new shell with a uvenv-managed virtualenv activated (any shell)")]
+    Shell(ShellOptions),
     #[clap(about = "Upgrade a package.")]
     Upgrade(UpgradeOptions),
     #[clap(about = "Upgrade all uvenv-installed packages.")]
@@ -330,10 +567,30 @@ pub enum Commands {
     Inject(InjectOptions),
     #[clap(aliases = &["eject"], about="Uninstall additional packages from a virtual environment managed by uvenv. (alias: `eject`)")]
     Uninject(UnInjectOptions),
+    #[clap(about = "Show which venv (and package) provides a command.")]
+    Which(WhichOptions),
     #[clap(about = "Check for possible issues and updates.")]
     Check(CheckOptions),
+    #[clap(
+        about = "Check installed packages for known vulnerabilities (using a local advisory database)."
+    )]
+    Audit(AuditOptions),
+    #[clap(about = "Show the licenses of all installed packages, grouped by license.")]
+    Licenses(LicensesOptions),
+    #[clap(
+        about = "Export a software bill of materials (CycloneDX or SPDX) for installed packages."
+    )]
+    Sbom(SbomOptions),
+    #[clap(about = "Show how much disk space venvs and the uv cache use.")]
+    Du(DuOptions),
+    #[clap(
+        about = "Remove leftovers of crashed or interrupted runs and installs, expired run venvs, and prune the uv cache."
+    )]
+    Gc(GcOptions),
 
-    #[clap(about = "Run a package in a temporary virtual environment.")]
+    #[clap(
+        about = "Run a package (or a script with inline metadata) in a temporary (cached) virtual environment."
+    )]
     Run(RunOptions),
     #[clap(about = "Run 'uv' in the right venv.")]
     Runuv(RunuvOptions),
@@ -341,36 +598,46 @@ pub enum Commands {
     Runpip(RunpipOptions),
     #[clap(about = "Run 'python' in the right venv.")]
     Runpython(RunpythonOptions),
+    #[clap(about = "Run any command in the right venv (with its bin directory on PATH).")]
+    Exec(ExecOptions),
     #[clap(
-        about = "Update ~/.bashrc with a PATH that includes the local bin directory that uvenv uses."
+        about = "Update your shell's rc file with a PATH that includes the local bin directory that uvenv uses."
     )]
     Ensurepath(EnsurepathOptions),
-    #[clap(about = "Use --install to install the autocomplete script (bash).")]
+    #[clap(about = "Use --install to install the autocomplete script (bash, zsh, fish).")]
     Completions(CompletionsOptions),
 
+    #[clap(
+        subcommand,
+        about = "Manage the Python interpreters used by your venvs."
+    )]
+    Python(PythonCommands),
+
+    #[clap(subcommand, about = "Manage uvenv's caches.")]
+    Cache(CacheCommands),
+
     #[clap(subcommand, about = "Manage uvenv itself.")]
     Self_(SelfCommands),
 }
 
-// todo `uvenv check`:
-//   - show missing metadata
-//   - show packages with updates
-//   - show packages with script issues
-
 impl Process for Commands {
     async fn process(self) -> anyhow::Result<i32> {
         match self {
             Self::List(opts) => opts.process().await,
+            Self::Tree(opts) => opts.process().await,
+            Self::Info(opts) => opts.process().await,
             Self::Install(opts) => opts.process().await,
             Self::Upgrade(opts) => opts.process().await,
             Self::Uninstall(opts) => opts.process().await,
             Self::Reinstall(opts) => opts.process().await,
             Self::Inject(opts) => opts.process().await,
             Self::Activate(opts) => opts.process().await,
+            Self::Shell(opts) => opts.process().await,
             Self::UpgradeAll(opts) => opts.process().await,
             Self::Runuv(opts) => opts.process().await,
             Self::Runpip(opts) => opts.process().await,
             Self::Runpython(opts) => opts.process().await,
+            Self::Exec(opts) => opts.process().await,
             Self::Ensurepath(opts) => opts.process().await,
             Self::UninstallAll(opts) => opts.process().await,
             Self::ReinstallAll(opts) => opts.process().await,
@@ -379,8 +646,16 @@ impl Process for Commands {
             Self::Run(opts) => opts.process().await,
             Self::Setup(opts) => opts.process().await,
             Self::Create(opts) => opts.process().await,
+            Self::Python(opts) => opts.process().await,
+            Self::Cache(opts) => opts.process().await,
             Self::Self_(opts) => opts.process().await,
             Self::Check(opts) => opts.process().await,
+            Self::Audit(opts) => opts.process().await,
+            Self::Licenses(opts) => opts.process().await,
+            Self::Sbom(opts) => opts.process().await,
+            Self::Du(opts) => opts.process().await,
+            Self::Which(opts) => opts.process().await,
+            Self::Gc(opts) => opts.process().await,
         }
     }
 }
@@ -438,3 +713,65 @@ impl Process for SelfCommands {
         }
     }
 }
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Parser)]
+pub struct PythonListOptions;
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Parser)]
+pub struct PythonMigrateOptions {
+    #[clap(
+        long,
+        help = "Python version (or executable) the venvs currently use, e.g. `3.11`"
+    )]
+    pub from: String,
+    #[clap(
+        long,
+        help = "Python version (or executable) to move the venvs to, e.g. `3.12`"
+    )]
+    pub to: String,
+    #[clap(long, help = "Only show which venvs would be migrated")]
+    pub dry_run: bool,
+    #[clap(long, help = "Run without `uv` cache")]
+    pub no_cache: bool,
+
+    #[clap(add = ArgValueCandidates::new(venv_names))]
+    pub venv_names: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Subcommand)]
+pub enum PythonCommands {
+    #[clap(about = "Show the Python interpreters in use and which venvs use them.")]
+    List(PythonListOptions),
+
+    #[clap(about = "Reinstall all venvs using one Python version with another one.")]
+    Migrate(PythonMigrateOptions),
+}
+
+impl Process for PythonCommands {
+    async fn process(self) -> anyhow::Result<i32> {
+        match self {
+            Self::List(opts) => opts.process().await,
+            Self::Migrate(opts) => opts.process().await,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Parser)]
+pub struct CachePruneRunsOptions {
+    #[clap(long, help = "Also remove run venvs that haven't expired yet")]
+    pub all: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Subcommand)]
+pub enum CacheCommands {
+    #[clap(about = "Remove expired (or with --all: every) cached `uvenv run` environment.")]
+    PruneRuns(CachePruneRunsOptions),
+}
+
+impl Process for CacheCommands {
+    async fn process(self) -> anyhow::Result<i32> {
+        match self {
+            Self::PruneRuns(opts) => opts.process().await,
+        }
+    }
+}
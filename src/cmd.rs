@@ -1,12 +1,17 @@
 use anyhow::anyhow;
+use core::future::Future;
 use std::env;
 use std::ffi::OsStr;
+use std::io::{self, Write};
+use std::os::unix::process::{CommandExt, ExitStatusExt};
 use std::path::PathBuf;
-use std::process::{Output, Stdio};
+use std::process::{ExitStatus, Output, Stdio};
 
-use owo_colors::OwoColorize;
 use tokio::fs::canonicalize;
 use tokio::process::Command;
+use tokio::signal::unix::{signal, SignalKind};
+
+use crate::errors::{CommandError, UvenvError};
 
 pub async fn find_sibling(name: &str) -> Option<PathBuf> {
     let Ok(binary_path) = &env::current_exe() else {
@@ -26,6 +31,30 @@ pub async fn find_sibling(name: &str) -> Option<PathBuf> {
     binary.exists().then_some(binary) // else None
 }
 
+/// Exit code of a finished process.
+/// Killed processes follow the shell convention of 128 + signal (e.g. 130 for SIGINT).
+pub fn exit_code(status: ExitStatus) -> i32 {
+    status
+        .code()
+        .or_else(|| status.signal().map(|signal| 128 + signal))
+        .unwrap_or(-1)
+}
+
+/// Replace the current process with `command` (via `execve`),
+/// so signals, job control and TTYs behave exactly as if it was started directly.
+/// This only returns if the command could not be started.
+pub fn exec_replace<S1: AsRef<OsStr>, S2: AsRef<OsStr>>(
+    command: S1,
+    args: &[S2],
+) -> anyhow::Result<i32> {
+    // anything still buffered would be lost after exec:
+    let _ = io::stdout().flush();
+
+    let err = std::process::Command::new(&command).args(args).exec();
+
+    Err(anyhow!(err).context(format!("Could not execute {:?}", command.as_ref())))
+}
+
 pub async fn run_print_output<S1: AsRef<OsStr>, S2: AsRef<OsStr>>(
     command: S1,
     args: &[S2],
@@ -34,80 +63,69 @@ pub async fn run_print_output<S1: AsRef<OsStr>, S2: AsRef<OsStr>>(
     cmd.args(args);
     cmd.stdout(Stdio::inherit());
     cmd.stderr(Stdio::inherit());
-    let code = cmd.status().await?;
+    let status = cmd.status().await?;
 
-    Ok(code.code().unwrap_or(-1))
+    Ok(exit_code(status))
 }
 
 pub async fn run_get_output<S1: AsRef<OsStr>, S2: AsRef<OsStr>>(
     command: S1,
     args: &[S2],
 ) -> anyhow::Result<String> {
-    let command_result = Command::new(command).args(args).output().await;
-
-    match command_result {
-        Ok(result) => match result.status.code() {
-            Some(0) => Ok(String::from_utf8(result.stdout).unwrap_or_default()),
-            Some(_) | None => Err(anyhow!(String::from_utf8(result.stderr).unwrap_or_default())),
-        },
-        Err(result_err) => Err(result_err.into()),
+    let name = command.as_ref().to_string_lossy().into_owned();
+    let result = Command::new(command).args(args).output().await?;
+
+    if result.status.success() {
+        Ok(String::from_utf8(result.stdout).unwrap_or_default())
+    } else {
+        let stderr = String::from_utf8(result.stderr).unwrap_or_default();
+        Err(command_error(&name, exit_code(result.status), stderr))
     }
 }
 
+/// Keep a failed command's stderr as a separate cause, classified by what went wrong.
+fn command_error(
+    command: &str,
+    exit_code: i32,
+    stderr: String,
+) -> anyhow::Error {
+    let kind = UvenvError::from_stderr(command, &stderr);
+    anyhow::Error::new(CommandError { exit_code, stderr }).context(kind)
+}
+
 pub async fn run<S1: AsRef<OsStr>, S2: AsRef<OsStr>>(
     script: S1,
     args: &[S2],
     err_prefix: Option<String>,
 ) -> anyhow::Result<bool> {
-    let command_result = Command::new(script).args(args).output().await;
-
-    #[expect(
-        clippy::option_if_let_else,
-        reason = "map_or_else complains about moved 'err'"
-    )]
-    match command_result {
-        Ok(Output { status, stderr, .. }) => {
-            if status.success() {
-                Ok(true)
-            } else {
-                Err(String::from_utf8(stderr).unwrap_or_default())
-            }
-        },
-        Err(result) => Err(result.to_string()),
-    } // if err, add prefix:
-    .map_err(|err| {
-        if let Some(prefix) = err_prefix {
-            anyhow!("{prefix} | {err}")
-        } else {
-            anyhow!(err)
-        }
-    })
-}
+    let name = err_prefix.unwrap_or_else(|| script.as_ref().to_string_lossy().into_owned());
 
-/// Given a target shell (e.g. 'bash'), run a positive or negative callback function.
-pub fn run_if_shell<T, Y: Fn(String) -> Option<T>, N: Fn(String) -> Option<T>>(
-    target: &str,
-    if_bash: Y,
-    if_not_bash: N,
-) -> Option<T> {
-    let shell = env::var("SHELL").ok().unwrap_or_default();
+    let Output { status, stderr, .. } = Command::new(script)
+        .args(args)
+        .kill_on_drop(true) // e.g. when cancelled by `cancel_on_signal`
+        .output()
+        .await
+        .map_err(|err| anyhow!(err).context(UvenvError::CommandFailed(name.clone())))?;
 
-    if shell.ends_with(target) {
-        if_bash(shell)
+    if status.success() {
+        Ok(true)
     } else {
-        if_not_bash(shell)
+        let stderr = String::from_utf8(stderr).unwrap_or_default();
+        Err(command_error(&name, exit_code(status), stderr))
     }
 }
 
-/// Run a callback function if bash, or show a message saying the user's shell is unsupported.
-pub fn run_if_bash_else_warn<T, Y: Fn(String) -> Option<T>>(if_bash: Y) -> Option<T> {
-    run_if_shell("bash", if_bash, |shell| {
-        eprintln!(
-            "Unsupported shell '{}'. Currently, only bash is supported.",
-            shell.blue()
-        );
-        None
-    })
+/// Await `promise`, but stop early on Ctrl-C or SIGTERM.
+/// Dropping the unfinished future still runs its destructors (e.g. `VenvGuard`),
+/// which wouldn't happen if the signal just killed the process.
+pub async fn cancel_on_signal<T, F: Future<Output = anyhow::Result<T>>>(
+    promise: F
+) -> anyhow::Result<T> {
+    let mut sigterm = signal(SignalKind::terminate())?;
+
+    tokio::select! {
+        result = promise => result,
+        _ = tokio::signal::ctrl_c() => Err(UvenvError::Interrupted.into()),
+        _ = sigterm.recv() => Err(UvenvError::Terminated.into()),
+    }
 }
-
-// todo: run_if_shell_async + run_if_bash_else_warn_async
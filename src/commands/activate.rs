@@ -1,35 +1,34 @@
 use crate::cli::{ActivateOptions, Process};
-use crate::cmd::run_if_bash_else_warn;
-use crate::commands::ensurepath::add_to_bashrc;
+use crate::commands::ensurepath::add_to_rc;
+use crate::helpers::PathAsStr;
+use crate::shells::SupportedShell;
 use owo_colors::OwoColorize;
 
-pub async fn generate_activate() -> &'static str {
-    // Used by `uvenv --generate bash activate _`
-    // note: only bash is supported right now!
-    include_str!("../shell/activate.sh")
+pub async fn generate_activate(shell: SupportedShell) -> &'static str {
+    // Used by `uvenv --generate <shell> activate _`
+    shell.activate_script()
 }
 
-pub async fn install_activate() -> anyhow::Result<()> {
-    let bash_code = r#"eval "$(uvenv --generate=bash activate _)""#;
-    // call eval instead of actually adding the bash function() to bashrc
+pub async fn install_activate(shell: SupportedShell) -> anyhow::Result<()> {
+    // call eval instead of actually adding the shell function to the rc file
     // so updates are available immediately
-    add_to_bashrc(bash_code, true).await
+    add_to_rc(shell, &shell.eval_line("activate _")).await
 }
 
 impl Process for ActivateOptions {
     async fn process(self) -> anyhow::Result<i32> {
-        // wait a minute, this is not a bash script!
+        // wait a minute, this is not a shell function!
         // show warning with setup info:
+        let Some(shell) = SupportedShell::detect_or_warn() else {
+            return Ok(126); // = cannot execute, if not a supported shell
+        };
 
-        Ok(
-            run_if_bash_else_warn(|shell| {
-                println!("Your shell ({}) is supported, but the shell extension is not set up.\n\
-                You can use `uvenv setup` to do this automatically, or add `{}` to your bashrc file to enable it manually.",
-                         &shell.blue(),
-                         r#"eval "$(uvenv --generate=bash activate _)""#.green()
-                );
-                Some(1)
-            }).unwrap_or(126) // = cannot execute, if not BASH
-        )
+        println!("Your shell ({}) is supported, but the shell extension is not set up.\n\
+        You can use `uvenv setup` to do this automatically, or add `{}` to {} to enable it manually.",
+                 shell.name().blue(),
+                 shell.eval_line("activate _").green(),
+                 shell.rc_file().as_str(),
+        );
+        Ok(1)
     }
 }
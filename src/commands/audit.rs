@@ -0,0 +1,345 @@
+use anyhow::{bail, Context};
+use core::str::FromStr;
+use owo_colors::OwoColorize;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+use uv_pep440::Version;
+use uv_pep508::PackageName;
+
+use crate::cli::{AuditCommands, AuditOptions, Process};
+use crate::commands::list::list_packages;
+use crate::config::load_config;
+use crate::errors::{GENERIC_EXIT_CODE, VULNERABILITIES_EXIT_CODE};
+use crate::helpers::PathAsStr;
+use crate::metadata::{venv_path, LoadMetadataConfig};
+use crate::output::{is_json, strip_ansi};
+use crate::uv::{environment_from_root, uv_freeze_environ};
+
+/// The parts of an OSV advisory (<https://ossf.github.io/osv-schema/>) that are used here.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Deserialize)]
+#[serde(default)]
+pub struct Advisory {
+    pub id: String,
+    pub aliases: Vec<String>,
+    pub affected: Vec<Affected>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default, Deserialize)]
+#[serde(default)]
+pub struct Affected {
+    pub package: AffectedPackage,
+    pub ranges: Vec<AffectedRange>,
+    /// explicitly listed affected versions
+    pub versions: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default, Deserialize)]
+#[serde(default)]
+pub struct AffectedPackage {
+    pub ecosystem: String,
+    pub name: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default, Deserialize)]
+#[serde(default)]
+pub struct AffectedRange {
+    #[serde(rename = "type")]
+    pub range_type: String,
+    pub events: Vec<RangeEvent>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default, Deserialize)]
+#[serde(default)]
+pub struct RangeEvent {
+    pub introduced: Option<String>,
+    pub fixed: Option<String>,
+    pub last_affected: Option<String>,
+}
+
+fn parse_version(version: &str) -> Option<Version> {
+    Version::from_str(version).ok()
+}
+
+impl AffectedRange {
+    /// If `version` falls within this range: `Some(fixed version)` (which can be empty if there is no fix yet).
+    fn matches(
+        &self,
+        version: &Version,
+    ) -> Option<String> {
+        if self.range_type != "ECOSYSTEM" {
+            // GIT ranges can't be compared to a PyPI version
+            return None;
+        }
+
+        let mut introduced: Option<Version> = None;
+        for event in &self.events {
+            if let Some(start) = &event.introduced {
+                // '0' means 'all versions'
+                introduced = parse_version(start);
+            } else if let (Some(start), Some(end)) = (&introduced, &event.fixed) {
+                if version >= start && parse_version(end).is_some_and(|fixed| version < &fixed) {
+                    return Some(end.clone());
+                }
+                introduced = None;
+            } else if let (Some(start), Some(end)) = (&introduced, &event.last_affected) {
+                if version >= start && parse_version(end).is_some_and(|last| version <= &last) {
+                    return Some(String::new());
+                }
+                introduced = None;
+            }
+        }
+
+        // introduced without an end: everything after it is affected
+        introduced
+            .is_some_and(|start| version >= &start)
+            .then(String::new)
+    }
+}
+
+impl Affected {
+    /// If `version` is affected: `Some(fixed version)` (which can be empty if there is no fix yet).
+    pub fn matches(
+        &self,
+        version: &str,
+    ) -> Option<String> {
+        let parsed = parse_version(version)?;
+
+        self.ranges
+            .iter()
+            .find_map(|range| range.matches(&parsed))
+            .or_else(|| {
+                self.versions
+                    .iter()
+                    .any(|affected| affected == version)
+                    .then(String::new)
+            })
+    }
+}
+
+/// Advisories by (normalized) package name.
+pub struct AdvisoryDatabase {
+    advisories: HashMap<PackageName, Vec<Advisory>>,
+}
+
+impl AdvisoryDatabase {
+    pub async fn load(path: &Path) -> anyhow::Result<Self> {
+        let Ok(mut entries) = tokio::fs::read_dir(path).await else {
+            bail!(
+                "No advisory database found at {}, run `{}` first.",
+                path.as_str().yellow(),
+                "uvenv audit update-db".green()
+            );
+        };
+
+        let mut advisories: HashMap<PackageName, Vec<Advisory>> = HashMap::new();
+        while let Some(entry) = entries.next_entry().await? {
+            let file = entry.path();
+            if file.extension().and_then(|ext| ext.to_str()) != Some("json") {
+                continue;
+            }
+
+            let content = tokio::fs::read(&file).await?;
+            let Ok(advisory) = serde_json::from_slice::<Advisory>(&content) else {
+                eprintln!("⚠️ Skipping invalid advisory {}", file.as_str().yellow());
+                continue;
+            };
+
+            let names: Vec<PackageName> = advisory
+                .affected
+                .iter()
+                .filter(|affected| affected.package.ecosystem == "PyPI")
+                .filter_map(|affected| PackageName::from_str(&affected.package.name).ok())
+                .collect();
+
+            for name in names {
+                advisories.entry(name).or_default().push(advisory.clone());
+            }
+        }
+
+        Ok(Self { advisories })
+    }
+
+    /// Advisories affecting `version` of `package`, with the version that fixes it (if any).
+    pub fn find(
+        &self,
+        package: &PackageName,
+        version: &str,
+    ) -> Vec<(&Advisory, String)> {
+        let Some(advisories) = self.advisories.get(package) else {
+            return Vec::new();
+        };
+
+        advisories
+            .iter()
+            .filter_map(|advisory| {
+                advisory
+                    .affected
+                    .iter()
+                    .filter(|affected| {
+                        affected.package.ecosystem == "PyPI"
+                            && PackageName::from_str(&affected.package.name)
+                                .is_ok_and(|name| &name == package)
+                    })
+                    .find_map(|affected| affected.matches(version))
+                    .map(|fixed| (advisory, fixed))
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize)]
+pub struct AuditFinding {
+    pub venv: String,
+    pub package: String,
+    pub installed: String,
+    pub advisory: String,
+    pub aliases: Vec<String>,
+    /// empty if there is no fixed version (yet)
+    pub fixed: String,
+}
+
+/// (name, version) for every package in `pip freeze` output (editable and url installs are skipped).
+fn parse_freeze(freeze: &str) -> Vec<(&str, &str)> {
+    freeze
+        .lines()
+        .filter_map(|line| line.split_once("=="))
+        .collect()
+}
+
+/// A venv whose packages could not be listed (so it wasn't audited).
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize)]
+pub struct AuditError {
+    pub venv: String,
+    pub error: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize)]
+pub struct AuditReport {
+    pub findings: Vec<AuditFinding>,
+    pub errors: Vec<AuditError>,
+}
+
+impl AuditReport {
+    /// Findings win over errors: an incomplete audit with vulnerabilities still has vulnerabilities.
+    pub fn exit_code(&self) -> i32 {
+        if !self.findings.is_empty() {
+            VULNERABILITIES_EXIT_CODE
+        } else if !self.errors.is_empty() {
+            GENERIC_EXIT_CODE
+        } else {
+            0
+        }
+    }
+}
+
+fn audit_venv(
+    database: &AdvisoryDatabase,
+    venv_name: &str,
+) -> anyhow::Result<Vec<AuditFinding>> {
+    let environ = environment_from_root(&venv_path(venv_name))?;
+    let freeze = uv_freeze_environ(&environ)?;
+
+    let mut findings = Vec::new();
+    for (name, version) in parse_freeze(&freeze) {
+        let Ok(package) = PackageName::from_str(name) else {
+            continue;
+        };
+
+        for (advisory, fixed) in database.find(&package, version) {
+            findings.push(AuditFinding {
+                venv: venv_name.to_owned(),
+                package: name.to_owned(),
+                installed: version.to_owned(),
+                advisory: advisory.id.clone(),
+                aliases: advisory.aliases.clone(),
+                fixed,
+            });
+        }
+    }
+
+    Ok(findings)
+}
+
+/// A broken venv is reported in `errors` without stopping the audit of the others.
+pub async fn audit(venv_names: &[String]) -> anyhow::Result<AuditReport> {
+    let config = load_config().await?;
+    let database = AdvisoryDatabase::load(&config.audit.database_path()).await?;
+
+    let items = list_packages(&LoadMetadataConfig::none(), Some(venv_names), None).await?;
+
+    let mut report = AuditReport::default();
+    for metadata in &items {
+        match audit_venv(&database, &metadata.name) {
+            Ok(findings) => report.findings.extend(findings),
+            Err(err) => report.errors.push(AuditError {
+                venv: metadata.name.clone(),
+                error: strip_ansi(&format!("{err:#}")),
+            }),
+        }
+    }
+
+    report.findings.sort();
+    Ok(report)
+}
+
+fn print_human(report: &AuditReport) {
+    for error in &report.errors {
+        eprintln!(
+            "⚠️ Could not audit '{}': {}",
+            error.venv.green(),
+            error.error.yellow()
+        );
+    }
+
+    let findings = &report.findings;
+    if findings.is_empty() {
+        if report.errors.is_empty() {
+            println!("{}", "✅ No known vulnerabilities found.".green().bold());
+        } else {
+            println!(
+                "{}",
+                "No known vulnerabilities found in the other venvs.".bold()
+            );
+        }
+        return;
+    }
+
+    println!("{}", "🚨 Vulnerabilities:".bold().underline());
+    for finding in findings {
+        let fixed = if finding.fixed.is_empty() {
+            "no fix available".red().to_string()
+        } else {
+            format!("fixed in {}", finding.fixed.green())
+        };
+
+        println!(
+            "  - {}: {} {} ({}, {})",
+            finding.venv.bold(),
+            finding.package.red(),
+            finding.installed,
+            finding.advisory.yellow(),
+            fixed
+        );
+    }
+}
+
+impl Process for AuditOptions {
+    async fn process(self) -> anyhow::Result<i32> {
+        if let Some(AuditCommands::UpdateDb(opts)) = self.command {
+            return opts.process().await;
+        }
+
+        let report = audit(&self.venv_names)
+            .await
+            .with_context(|| "Something went wrong while auditing;")?;
+
+        if self.json || is_json() {
+            println!("{}", serde_json::to_string_pretty(&report)?);
+        } else {
+            print_human(&report);
+        }
+
+        Ok(report.exit_code())
+    }
+}
@@ -0,0 +1,125 @@
+use anyhow::{anyhow, bail, Context};
+use owo_colors::OwoColorize;
+use std::ffi::OsStr;
+use std::io::{Cursor, Read};
+use std::path::{Path, PathBuf};
+
+use crate::cli::{AuditUpdateDbOptions, Process};
+use crate::config::load_config;
+use crate::errors::UvenvError;
+use crate::helpers::PathAsStr;
+use crate::offline::is_offline;
+
+async fn download(url: &str) -> anyhow::Result<Vec<u8>> {
+    let network_error =
+        |err: reqwest::Error| anyhow!(err).context(UvenvError::NetworkError(url.to_owned()));
+
+    let resp = reqwest::get(url)
+        .await
+        .and_then(reqwest::Response::error_for_status)
+        .map_err(network_error)?;
+
+    let bytes = resp.bytes().await.map_err(network_error)?;
+    Ok(bytes.to_vec())
+}
+
+/// Write the json files from a zip archive to `target`, returns the amount of advisories.
+fn extract_advisories(
+    archive: Vec<u8>,
+    target: &Path,
+) -> anyhow::Result<usize> {
+    let mut zip = zip::ZipArchive::new(Cursor::new(archive))?;
+    std::fs::create_dir_all(target)?;
+
+    let mut count = 0;
+    for idx in 0..zip.len() {
+        let mut file = zip.by_index(idx)?;
+
+        // `enclosed_name` protects against paths like '../../etc/passwd':
+        let Some(name) = file
+            .enclosed_name()
+            .and_then(|path| path.file_name().map(PathBuf::from))
+        else {
+            continue;
+        };
+        if name.extension().and_then(|ext| ext.to_str()) != Some("json") {
+            continue;
+        }
+
+        let mut content = Vec::new();
+        file.read_to_end(&mut content)?;
+        std::fs::write(target.join(name), content)?;
+        count += 1;
+    }
+
+    Ok(count)
+}
+
+/// The database is a directory with only `.json` files (or nothing at all yet).
+/// Anything else is not replaced, so a misconfigured path can't wipe an unrelated directory.
+fn looks_like_advisory_db(path: &Path) -> bool {
+    std::fs::read_dir(path).is_ok_and(|entries| {
+        entries.flatten().all(|entry| {
+            let path = entry.path();
+            path.is_file() && path.extension().and_then(OsStr::to_str) == Some("json")
+        })
+    })
+}
+
+pub async fn update_db() -> anyhow::Result<(PathBuf, usize)> {
+    if is_offline() {
+        bail!("The advisory database can't be updated in offline mode.");
+    }
+
+    let config = load_config().await?;
+    let database = config.audit.database_path();
+    let url = config.audit.database_url();
+
+    if database.exists() && !looks_like_advisory_db(&database) {
+        bail!(
+            "{} doesn't look like an advisory database (it should only contain .json files), refusing to replace it.",
+            database.as_str()
+        );
+    }
+
+    let archive = download(url)
+        .await
+        .with_context(|| format!("Could not download {url}"))?;
+
+    // extract next to the current database first, so a failed update doesn't leave it half-empty.
+    // the staging dir is removed when dropped, including the previous database:
+    let parent = database
+        .parent()
+        .with_context(|| format!("Invalid database path {}", database.as_str()))?;
+    tokio::fs::create_dir_all(parent).await?;
+    let staging = tempfile::Builder::new()
+        .prefix(".advisories-")
+        .tempdir_in(parent)?;
+
+    let target = staging.path().join("new");
+    let extract_to = target.clone();
+    let count =
+        tokio::task::spawn_blocking(move || extract_advisories(archive, &extract_to)).await??;
+
+    if database.exists() {
+        tokio::fs::rename(&database, staging.path().join("previous")).await?;
+    }
+    tokio::fs::rename(&target, &database).await?;
+
+    Ok((database, count))
+}
+
+impl Process for AuditUpdateDbOptions {
+    async fn process(self) -> anyhow::Result<i32> {
+        let (database, count) = update_db()
+            .await
+            .with_context(|| "Something went wrong while updating the advisory database;")?;
+
+        println!(
+            "🛡️  Stored {} advisories in {}",
+            count.to_string().green(),
+            database.as_str().blue()
+        );
+        Ok(0)
+    }
+}
@@ -0,0 +1,62 @@
+use anyhow::Context;
+use owo_colors::OwoColorize;
+
+use crate::cli::{CachePruneRunsOptions, Process};
+use crate::metadata::get_run_dir;
+use crate::run_cache::{RunEnvLock, RunMetadata};
+use crate::venv::remove_venv;
+
+/// Remove cached run environments that are expired (or all of them),
+/// skipping the ones that are currently in use.
+pub async fn prune_runs(all: bool) -> anyhow::Result<Vec<String>> {
+    let mut removed = Vec::new();
+
+    let Ok(run_dir) = std::fs::read_dir(get_run_dir()) else {
+        // nothing has been cached yet
+        return Ok(removed);
+    };
+
+    for entry in run_dir.flatten() {
+        let path = entry.path();
+        if !path.is_dir() {
+            // lock files
+            continue;
+        }
+
+        let key = entry.file_name().into_string().unwrap_or_default();
+
+        let Some(_lock) = RunEnvLock::try_exclusive(&key)? else {
+            // in use (or being built) by a `uvenv run` right now
+            continue;
+        };
+
+        let expired = RunMetadata::for_dir(&path)
+            .await
+            .map_or(true, |meta| meta.is_expired());
+
+        if all || expired {
+            remove_venv(&path).await?;
+            removed.push(key);
+        }
+    }
+
+    Ok(removed)
+}
+
+impl Process for CachePruneRunsOptions {
+    async fn process(self) -> anyhow::Result<i32> {
+        let removed = prune_runs(self.all)
+            .await
+            .with_context(|| "Something went wrong while pruning run environments;")?;
+
+        if removed.is_empty() {
+            println!("🌟 No cached run environments to remove.");
+        } else {
+            for key in &removed {
+                println!("🗑️  {} removed!", key.yellow());
+            }
+        }
+
+        Ok(0)
+    }
+}
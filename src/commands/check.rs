@@ -1,11 +1,72 @@
+use core::str::FromStr;
 use owo_colors::OwoColorize;
 use std::collections::BTreeMap;
+use std::path::PathBuf;
 
 use serde::{Deserialize, Serialize};
+use uv_installer::SitePackages;
+use uv_pep508::PackageName;
 
 use crate::cli::{CheckOptions, Process};
+use crate::commands::info::direct_url;
+use crate::commands::install::install_symlinks;
 use crate::commands::list::list_packages;
-use crate::metadata::LoadMetadataConfig;
+use crate::commands::reinstall::reinstall;
+use crate::commands::tree::unrequired_packages;
+use crate::commands::upgrade::{update_metadata, upgrade_package};
+use crate::helpers::{fmt_error, PathAsStr, PathToString};
+use crate::metadata::{find_venvs_without_metadata, venv_path, LoadMetadataConfig, Metadata};
+use crate::output::is_json;
+use crate::pip::parse_requirement;
+use crate::pypi::YankedRelease;
+use crate::run_cache::{find_stale_run_envs, remove_stale_run_env};
+use crate::symlinks::find_orphaned_symlinks;
+use crate::uv::{environment_from_root, uv_get_installed_version};
+use crate::venv::{activate_venv, setup_environ_from_requirement};
+
+/// Machine-readable kind of problem, used in the JSON output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum IssueCode {
+    Outdated,
+    MissingScript,
+    StaleRun,
+    OrphanedSymlink,
+    MissingMetadata,
+    VersionMismatch,
+    Yanked,
+}
+
+/// A single problem found by `uvenv check`.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct Finding {
+    pub code: IssueCode,
+    /// venv, script or path the issue is about
+    pub subject: String,
+    #[serde(default)]
+    pub detail: String,
+}
+
+impl Finding {
+    pub fn new(
+        code: IssueCode,
+        subject: &str,
+        detail: &str,
+    ) -> Self {
+        Self {
+            code,
+            subject: subject.to_owned(),
+            detail: detail.to_owned(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize, Deserialize)]
+struct VersionMismatch {
+    recorded: String,
+    /// empty if the package is not installed at all anymore
+    installed: String,
+}
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize, Deserialize)]
 struct Issues<'metadata> {
@@ -13,6 +74,64 @@ struct Issues<'metadata> {
     outdated: Vec<&'metadata str>,
     #[serde(borrow)]
     scripts: BTreeMap<&'metadata str, Vec<String>>,
+    #[serde(default)]
+    stale_runs: Vec<String>,
+    #[serde(default)]
+    orphaned_symlinks: Vec<String>,
+    #[serde(default)]
+    missing_metadata: Vec<String>,
+    #[serde(borrow, default)]
+    version_mismatch: BTreeMap<&'metadata str, VersionMismatch>,
+    #[serde(borrow, default)]
+    yanked: BTreeMap<&'metadata str, Vec<YankedRelease>>,
+}
+
+/// JSON output: the issues per category, plus a flat list with an issue code per finding.
+#[derive(Serialize)]
+struct Report<'issues, 'metadata> {
+    #[serde(flatten)]
+    issues: &'issues Issues<'metadata>,
+    findings: Vec<Finding>,
+    /// only with `check --fix`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    fixes: Option<Vec<AppliedFix>>,
+}
+
+/// Outcome of a single fix, used in the JSON output of `check --fix`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+struct AppliedFix {
+    action: &'static str,
+    /// venv or path the fix is about
+    subject: String,
+    status: FixStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+enum FixStatus {
+    /// `--dry-run`: would be applied
+    Planned,
+    Done,
+    Failed,
+}
+
+/// The version in site-packages, if it doesn't match the one recorded in `.metadata`.
+fn find_version_mismatch(metadata: &Metadata) -> Option<VersionMismatch> {
+    if metadata.install_spec.is_empty() || metadata.installed_version.is_empty() {
+        // e.g. `uvenv create`: no main package
+        return None;
+    }
+
+    let package_name = PackageName::from_str(&metadata.name).ok()?;
+    let venv = environment_from_root(&venv_path(&metadata.name)).ok()?;
+    let installed = uv_get_installed_version(&package_name, Some(&venv)).unwrap_or_default();
+
+    (installed != metadata.installed_version).then(|| VersionMismatch {
+        recorded: metadata.installed_version.clone(),
+        installed,
+    })
 }
 
 impl<'metadata> Issues<'metadata> {
@@ -20,6 +139,11 @@ impl<'metadata> Issues<'metadata> {
         Self {
             outdated: Vec::new(),
             scripts: BTreeMap::new(),
+            stale_runs: Vec::new(),
+            orphaned_symlinks: Vec::new(),
+            missing_metadata: Vec::new(),
+            version_mismatch: BTreeMap::new(),
+            yanked: BTreeMap::new(),
         }
     }
 
@@ -34,18 +158,87 @@ impl<'metadata> Issues<'metadata> {
             .fold(0, |acc, vec| acc + vec.len() as i32)
     }
 
+    #[expect(clippy::as_conversions, reason = "The number won't be that big")]
+    pub fn count_stale_runs(&self) -> i32 {
+        self.stale_runs.len() as i32
+    }
+
+    #[expect(clippy::as_conversions, reason = "The number won't be that big")]
+    pub fn count_broken_venvs(&self) -> i32 {
+        (self.orphaned_symlinks.len() + self.missing_metadata.len() + self.version_mismatch.len())
+            as i32
+    }
+
+    #[expect(clippy::as_conversions, reason = "The number won't be that big")]
+    pub fn count_yanked(&self) -> i32 {
+        self.yanked
+            .values()
+            .fold(0, |acc, vec| acc + vec.len() as i32)
+    }
+
     pub fn count(&self) -> i32 {
-        self.count_outdated() + self.count_scripts()
+        self.count_outdated()
+            + self.count_scripts()
+            + self.count_stale_runs()
+            + self.count_broken_venvs()
+            + self.count_yanked()
     }
 
-    pub fn print_json(&self) -> anyhow::Result<i32> {
-        let json = serde_json::to_string_pretty(self)?;
+    pub fn findings(&self) -> Vec<Finding> {
+        let mut findings = Vec::new();
 
-        eprintln!("{json}");
+        for venv in &self.outdated {
+            findings.push(Finding::new(IssueCode::Outdated, venv, ""));
+        }
+        for (venv, scripts) in &self.scripts {
+            for script in scripts {
+                findings.push(Finding::new(IssueCode::MissingScript, venv, script));
+            }
+        }
+        for path in &self.stale_runs {
+            findings.push(Finding::new(IssueCode::StaleRun, path, ""));
+        }
+        for path in &self.orphaned_symlinks {
+            findings.push(Finding::new(IssueCode::OrphanedSymlink, path, ""));
+        }
+        for venv in &self.missing_metadata {
+            findings.push(Finding::new(IssueCode::MissingMetadata, venv, ""));
+        }
+        for (venv, mismatch) in &self.version_mismatch {
+            let detail = format!("{} != {}", mismatch.recorded, mismatch.installed);
+            findings.push(Finding::new(IssueCode::VersionMismatch, venv, &detail));
+        }
+        for (venv, releases) in &self.yanked {
+            for release in releases {
+                findings.push(Finding::new(IssueCode::Yanked, venv, &release.to_string()));
+            }
+        }
+
+        findings
+    }
+
+    pub fn print_json(&self) -> anyhow::Result<i32> {
+        self.print_report(None)?;
 
         Ok(self.count())
     }
 
+    fn print_report(
+        &self,
+        fixes: Option<Vec<AppliedFix>>,
+    ) -> anyhow::Result<()> {
+        let report = Report {
+            issues: self,
+            findings: self.findings(),
+            fixes,
+        };
+        let json = serde_json::to_string_pretty(&report)?;
+
+        println!("{json}");
+
+        Ok(())
+    }
+
     fn print_human(&self) -> i32 {
         let issue_count = self.count();
 
@@ -83,8 +276,327 @@ impl<'metadata> Issues<'metadata> {
             println!("{}", "💡 Tip: you can use `uvenv reinstall <package>` to reinstall an environment, which might fix the missing scripts.".blue());
         }
 
+        // Display leftovers of crashed runs
+        if !self.stale_runs.is_empty() {
+            println!("{}", "\n🔶 Stale Run Environments:".bold().yellow());
+            for venv in &self.stale_runs {
+                println!("  - {}", venv.red());
+            }
+
+            println!(
+                "{}",
+                "💡 Tip: you can use `uvenv gc` to remove these.".blue()
+            );
+        }
+
+        // Display symlinks into removed venvs
+        if !self.orphaned_symlinks.is_empty() {
+            println!("{}", "\n🔶 Orphaned Symlinks:".bold().yellow());
+            for symlink in &self.orphaned_symlinks {
+                println!("  - {}", symlink.red());
+            }
+
+            println!(
+                "{}",
+                "💡 Tip: the venvs these point to don't exist anymore, so they can be removed (or reinstall the package)."
+                    .blue()
+            );
+        }
+
+        // Display venvs that `list` can't show
+        if !self.missing_metadata.is_empty() {
+            println!("{}", "\n🔶 Missing Metadata:".bold().yellow());
+            for venv in &self.missing_metadata {
+                println!("  - {}", venv.red());
+            }
+
+            println!(
+                "{}",
+                "💡 Tip: you can use `uvenv check --fix` to rebuild the metadata from the venv, or `uvenv install --force <package>` to recreate the environment."
+                    .blue()
+            );
+        }
+
+        // Display packages changed behind uvenv's back (e.g. via `uvenv runpip`)
+        if !self.version_mismatch.is_empty() {
+            println!("{}", "\n🔶 Version Mismatch:".bold().yellow());
+            for (venv, mismatch) in &self.version_mismatch {
+                let installed = if mismatch.installed.is_empty() {
+                    "not installed"
+                } else {
+                    mismatch.installed.as_str()
+                };
+                println!(
+                    "  - {}: recorded {}, installed {}",
+                    venv.red().bold(),
+                    mismatch.recorded.yellow(),
+                    installed.red()
+                );
+            }
+
+            println!(
+                "{}",
+                "💡 Tip: you can use `uvenv reinstall <package>` to get the metadata back in sync."
+                    .blue()
+            );
+        }
+
+        // Display installed releases that were pulled from the index (usually for serious bugs)
+        if !self.yanked.is_empty() {
+            println!("{}", "\n🔶 Yanked Releases:".bold().yellow());
+            for (venv, releases) in &self.yanked {
+                println!("  - {}", format!("{venv}:").red().bold());
+                for release in releases {
+                    println!("    - {}", release.to_string().red());
+                }
+            }
+
+            println!(
+                "{}",
+                "💡 Tip: you can use `uvenv upgrade <package>` (or `uvenv runpip <package> install <dependency>==<version>`) to move away from a yanked release."
+                    .blue()
+            );
+        }
+
         issue_count
     }
+
+    fn plan_fixes(
+        &self,
+        upgrade: bool,
+    ) -> Vec<FixAction> {
+        let mut actions = Vec::new();
+
+        // venvs where the package itself is gone are recreated (which includes their scripts):
+        let to_recreate: Vec<&str> = self
+            .version_mismatch
+            .iter()
+            .filter(|(_, mismatch)| mismatch.installed.is_empty())
+            .map(|(venv, _)| *venv)
+            .collect();
+
+        for venv in &to_recreate {
+            actions.push(FixAction::Recreate((*venv).to_owned()));
+        }
+        for (venv, mismatch) in &self.version_mismatch {
+            if !mismatch.installed.is_empty() {
+                actions.push(FixAction::SyncVersion((*venv).to_owned()));
+            }
+        }
+        for venv in &self.missing_metadata {
+            actions.push(FixAction::RebuildMetadata(venv.clone()));
+        }
+        for venv in self.scripts.keys() {
+            if !to_recreate.contains(venv) {
+                actions.push(FixAction::Relink((*venv).to_owned()));
+            }
+        }
+        if upgrade {
+            for venv in &self.outdated {
+                if !to_recreate.contains(venv) {
+                    actions.push(FixAction::Upgrade((*venv).to_owned()));
+                }
+            }
+        }
+        for symlink in &self.orphaned_symlinks {
+            actions.push(FixAction::RemoveSymlink(PathBuf::from(symlink)));
+        }
+        for venv in &self.stale_runs {
+            actions.push(FixAction::RemoveStaleRun(PathBuf::from(venv)));
+        }
+
+        actions
+    }
+
+    /// Resolve the issues (or only show the plan with `dry_run`).
+    /// With `json`, the findings and the outcome per fix are printed as one JSON object.
+    /// Returns the amount of issues that could not be fixed.
+    async fn fix(
+        &self,
+        upgrade: bool,
+        dry_run: bool,
+        json: bool,
+    ) -> anyhow::Result<i32> {
+        let actions = self.plan_fixes(upgrade);
+
+        if actions.is_empty() && !json {
+            println!("{}", "✅ Nothing to fix!".green().bold());
+        }
+
+        let mut failed = 0;
+        let mut applied = Vec::with_capacity(actions.len());
+        for action in &actions {
+            if dry_run {
+                if !json {
+                    println!("📝 Would {}", action.describe());
+                }
+                applied.push(action.outcome(FixStatus::Planned, None));
+                continue;
+            }
+
+            match action.apply().await {
+                Ok(()) => {
+                    if !json {
+                        println!("🔧 {}: done", action.describe());
+                    }
+                    applied.push(action.outcome(FixStatus::Done, None));
+                },
+                Err(msg) => {
+                    failed += 1;
+                    applied.push(action.outcome(FixStatus::Failed, Some(format!("{msg:#}"))));
+                    if !json {
+                        let msg = msg.context(format!("Could not {}", action.describe()));
+                        eprintln!("{}", fmt_error(&msg));
+                    }
+                },
+            }
+        }
+
+        if json {
+            self.print_report(Some(applied))?;
+        } else if !upgrade && !self.outdated.is_empty() {
+            println!(
+                "{}",
+                "💡 Tip: outdated packages are only upgraded with `uvenv check --fix --upgrade`."
+                    .blue()
+            );
+        }
+
+        Ok(failed)
+    }
+}
+
+/// A way to resolve (part of) an issue found by `check`.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+enum FixAction {
+    Relink(String),
+    Upgrade(String),
+    RemoveStaleRun(PathBuf),
+    RemoveSymlink(PathBuf),
+    RebuildMetadata(String),
+    SyncVersion(String),
+    Recreate(String),
+}
+
+impl FixAction {
+    /// Machine-readable kind of fix, used in the JSON output.
+    const fn code(&self) -> &'static str {
+        match self {
+            Self::Relink(_) => "relink",
+            Self::Upgrade(_) => "upgrade",
+            Self::RemoveStaleRun(_) => "remove-stale-run",
+            Self::RemoveSymlink(_) => "remove-symlink",
+            Self::RebuildMetadata(_) => "rebuild-metadata",
+            Self::SyncVersion(_) => "sync-version",
+            Self::Recreate(_) => "recreate",
+        }
+    }
+
+    fn subject(&self) -> String {
+        match self {
+            Self::Relink(venv)
+            | Self::Upgrade(venv)
+            | Self::RebuildMetadata(venv)
+            | Self::SyncVersion(venv)
+            | Self::Recreate(venv) => venv.clone(),
+            Self::RemoveStaleRun(path) | Self::RemoveSymlink(path) => path.as_str().to_owned(),
+        }
+    }
+
+    fn outcome(
+        &self,
+        status: FixStatus,
+        error: Option<String>,
+    ) -> AppliedFix {
+        AppliedFix {
+            action: self.code(),
+            subject: self.subject(),
+            status,
+            error,
+        }
+    }
+
+    fn describe(&self) -> String {
+        match self {
+            Self::Relink(venv) => format!("relink the scripts of {}", venv.green()),
+            Self::Upgrade(venv) => format!("upgrade {}", venv.green()),
+            Self::RemoveStaleRun(path) => {
+                format!("remove stale run environment {}", path.as_str().yellow())
+            },
+            Self::RemoveSymlink(path) => {
+                format!("remove orphaned symlink {}", path.as_str().yellow())
+            },
+            Self::RebuildMetadata(venv) => format!("rebuild the metadata of {}", venv.green()),
+            Self::SyncVersion(venv) => format!("update the recorded version of {}", venv.green()),
+            Self::Recreate(venv) => format!("recreate {}", venv.green()),
+        }
+    }
+
+    async fn apply(&self) -> anyhow::Result<()> {
+        match self {
+            Self::Relink(venv) => relink_scripts(venv).await,
+            Self::Upgrade(venv) => upgrade_package(venv, false, false, false).await.map(drop),
+            Self::RemoveStaleRun(path) => remove_stale_run_env(path).await.map(drop),
+            Self::RemoveSymlink(path) => Ok(tokio::fs::remove_file(path).await?),
+            Self::RebuildMetadata(venv) => rebuild_metadata(venv).await,
+            Self::SyncVersion(venv) => sync_installed_version(venv).await,
+            Self::Recreate(venv) => reinstall(venv, None, true, true, false, false)
+                .await
+                .map(drop),
+        }
+    }
+}
+
+/// Link the scripts of a venv again (e.g. after they were removed from the bin dir).
+async fn relink_scripts(venv_name: &str) -> anyhow::Result<()> {
+    let (requirement, environ) = setup_environ_from_requirement(venv_name).await?;
+    let mut metadata = Metadata::for_requirement(&requirement, &LoadMetadataConfig::none()).await?;
+
+    install_symlinks(&mut metadata, &environ, &requirement, false).await
+}
+
+/// Packages that `uv venv --seed` installs, so they are not counted as injected.
+const SEED_PACKAGES: [&str; 3] = ["pip", "setuptools", "wheel"];
+
+/// Create a `.metadata` file based on what is installed in the venv.
+/// The original version constraint can't be recovered, so `requested_version` stays empty.
+async fn rebuild_metadata(venv_name: &str) -> anyhow::Result<()> {
+    let (requirement, _) = parse_requirement(venv_name).await?;
+    // `Metadata::find` looks at the active venv:
+    let environ = activate_venv(&venv_path(venv_name)).await?;
+
+    let mut metadata = Metadata::find(&requirement);
+
+    // installed from a url or path instead of the index:
+    let site_packages = SitePackages::from_environment(&environ)?;
+    if let Some(url) = site_packages
+        .get_packages(&requirement.name)
+        .into_iter()
+        .next()
+        .and_then(direct_url)
+    {
+        metadata.install_spec = format!("{} @ {url}", requirement.name);
+    }
+
+    metadata.injected = unrequired_packages(&environ, &requirement.name)?
+        .iter()
+        .map(ToString::to_string)
+        .filter(|package| !SEED_PACKAGES.contains(&package.as_str()))
+        .collect();
+
+    // also stores the metadata:
+    install_symlinks(&mut metadata, &environ, &requirement, false).await
+}
+
+/// Record the version that is actually installed (e.g. after `uvenv runpip install --upgrade`).
+async fn sync_installed_version(venv_name: &str) -> anyhow::Result<()> {
+    let (requirement, environ) = setup_environ_from_requirement(venv_name).await?;
+    let mut metadata = Metadata::for_requirement(&requirement, &LoadMetadataConfig::none()).await?;
+
+    let requested_version = metadata.requested_version.clone();
+    update_metadata(&mut metadata, &requirement, &environ, requested_version).await?;
+
+    Ok(())
 }
 
 impl CheckOptions {
@@ -94,6 +606,7 @@ impl CheckOptions {
             updates_check: !self.skip_updates,
             updates_prereleases: self.show_prereleases,
             updates_ignore_constraints: self.ignore_constraints,
+            yanked_check: !self.skip_updates,
         }
     }
 }
@@ -115,9 +628,37 @@ impl Process for CheckOptions {
             if !self.skip_updates && metadata.outdated {
                 issues.outdated.push(&metadata.name);
             }
+
+            if !self.skip_updates && !metadata.yanked.is_empty() {
+                issues
+                    .yanked
+                    .insert(&metadata.name, metadata.yanked.clone());
+            }
+
+            if let Some(mismatch) = find_version_mismatch(metadata) {
+                issues.version_mismatch.insert(&metadata.name, mismatch);
+            }
         }
 
-        if self.json {
+        issues.missing_metadata = find_venvs_without_metadata(&self.venv_names);
+
+        if !self.skip_scripts {
+            issues.orphaned_symlinks = find_orphaned_symlinks()
+                .into_iter()
+                .map(PathToString::to_string)
+                .collect();
+        }
+
+        issues.stale_runs = find_stale_run_envs()
+            .into_iter()
+            .map(PathToString::to_string)
+            .collect();
+
+        if self.fix {
+            issues
+                .fix(self.upgrade, self.dry_run, self.json || is_json())
+                .await
+        } else if self.json || is_json() {
             issues.print_json()
         } else {
             Ok(issues.print_human())
@@ -1,34 +1,39 @@
 use crate::cli::{CompletionsOptions, Process};
 use anyhow::Context;
 
-use crate::cmd::run_if_bash_else_warn;
+use crate::helpers::PathAsStr;
+use crate::shells::SupportedShell;
 use owo_colors::OwoColorize;
 
-use super::ensurepath::add_to_bashrc;
+use super::ensurepath::add_to_rc;
 
-pub async fn completions(install: bool) -> anyhow::Result<i32> {
-    let for_bash = r#"eval "$(uvenv --generate=bash completions)""#;
+pub async fn completions(
+    shell: SupportedShell,
+    install: bool,
+) -> anyhow::Result<i32> {
+    let code = shell.eval_line("completions");
 
     if install {
         // you probably want `uvenv setup` but keep this for legacy.
-        add_to_bashrc(for_bash, true).await?;
-        Ok(0)
+        add_to_rc(shell, &code).await?;
     } else {
-        Ok(run_if_bash_else_warn(|_shell| {
-            eprintln!(
-                "Tip: place this line in your ~/.bashrc or run '{}' to do this automatically!",
-                "uvenv setup".green()
-            );
-            println!("{for_bash}");
-            Some(0)
-        })
-        .unwrap_or(1))
+        eprintln!(
+            "Tip: place this line in {} or run '{}' to do this automatically!",
+            shell.rc_file().as_str(),
+            "uvenv setup".green()
+        );
+        println!("{code}");
     }
+    Ok(0)
 }
 
 impl Process for CompletionsOptions {
     async fn process(self) -> anyhow::Result<i32> {
-        completions(self.install)
+        let Some(shell) = SupportedShell::detect_or_warn() else {
+            return Ok(1);
+        };
+
+        completions(shell, self.install)
             .await
             .with_context(|| "Something went wrong trying to generate or install completions;")
     }
@@ -1,5 +1,6 @@
 use crate::cli::{CreateOptions, Process};
 use crate::metadata::{get_venv_dir, Metadata};
+use crate::output::{Action, ActionResult};
 use crate::venv::{activate_venv, create_venv_raw};
 use anyhow::Context;
 use owo_colors::OwoColorize;
@@ -9,7 +10,7 @@ pub async fn create(
     python: Option<&String>,
     seed: bool,
     force: bool,
-) -> anyhow::Result<String> {
+) -> anyhow::Result<ActionResult> {
     let venv_path = get_venv_dir().join(name);
 
     create_venv_raw(&venv_path, python, force, seed).await?;
@@ -22,7 +23,8 @@ pub async fn create(
 
     metadata.save(&venv_path).await?;
 
-    Ok(format!("🏗️ Succesfully created '{}'!", name.green()))
+    Ok(ActionResult::new(Action::Create, name)
+        .with_message(format!("🏗️ Succesfully created '{}'!", name.green())))
 }
 
 impl Process for CreateOptions {
@@ -35,8 +37,8 @@ impl Process for CreateOptions {
         )
         .await
         {
-            Ok(msg) => {
-                println!("{msg}");
+            Ok(result) => {
+                result.print()?;
                 Ok(0)
             },
             Err(msg) => Err(msg).with_context(|| {
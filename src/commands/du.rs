@@ -0,0 +1,211 @@
+use anyhow::Context;
+use owo_colors::OwoColorize;
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use std::os::unix::fs::MetadataExt;
+use std::path::{Path, PathBuf};
+use uv_installer::SitePackages;
+
+use crate::cli::{DuOptions, Process};
+use crate::commands::list::list_packages;
+use crate::helpers::{dir_size, format_size, PathAsStr};
+use crate::metadata::{venv_path, LoadMetadataConfig};
+use crate::output::is_json;
+use crate::uv::{environment_from_root, uv_cache};
+
+/// A file installed by a package, as listed in its RECORD.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct RecordedFile {
+    hash: String,
+    size: u64,
+}
+
+/// Parse a RECORD line (`path,sha256=...,size`) into (path, hash).
+/// The path can contain commas, so split from the right.
+pub fn parse_record_line(line: &str) -> Option<(&str, &str)> {
+    let mut parts = line.rsplitn(3, ',');
+    parts.next()?; // size, but the actual file size is used instead
+    let hash = parts.next().filter(|hash| !hash.is_empty())?;
+    let path = parts.next()?.trim_matches('"');
+
+    Some((path, hash))
+}
+
+/// The dist-info directories of the packages installed in a venv.
+fn dist_info_dirs(venv_root: &Path) -> anyhow::Result<Vec<PathBuf>> {
+    let environ = environment_from_root(venv_root)?;
+    let site_packages = SitePackages::from_environment(&environ)?;
+
+    Ok(site_packages
+        .iter()
+        .map(|dist| dist.path().to_path_buf())
+        .collect())
+}
+
+/// Like `dist_info_dirs`, but by looking in lib/python*/site-packages on disk,
+/// e.g. when the python the venv was created with doesn't exist anymore.
+fn walk_dist_info_dirs(venv_root: &Path) -> Vec<PathBuf> {
+    let Ok(lib) = std::fs::read_dir(venv_root.join("lib")) else {
+        return Vec::new();
+    };
+
+    lib.flatten()
+        .filter_map(|python| std::fs::read_dir(python.path().join("site-packages")).ok())
+        .flat_map(|site_packages| site_packages.flatten().map(|entry| entry.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "dist-info"))
+        .collect()
+}
+
+/// Files in a venv that are not hardlinked yet, so they could be deduplicated if a copy exists elsewhere.
+/// Files that uv hardlinked from its cache already share their data (nlink > 1).
+fn unshared_files(venv_root: &Path) -> Vec<RecordedFile> {
+    let dist_infos = dist_info_dirs(venv_root).unwrap_or_else(|_| walk_dist_info_dirs(venv_root));
+
+    let mut files = Vec::new();
+    for dist_info in dist_infos {
+        let (Some(base), Ok(record)) = (
+            dist_info.parent(),
+            std::fs::read_to_string(dist_info.join("RECORD")),
+        ) else {
+            continue;
+        };
+
+        for (path, hash) in record.lines().filter_map(parse_record_line) {
+            let Ok(meta) = std::fs::symlink_metadata(base.join(path)) else {
+                continue;
+            };
+
+            if meta.is_file() && meta.nlink() == 1 {
+                files.push(RecordedFile {
+                    hash: hash.to_owned(),
+                    size: meta.len(),
+                });
+            }
+        }
+    }
+
+    files
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize)]
+pub struct VenvUsage {
+    pub name: String,
+    pub size: u64,
+    /// bytes in files that have an identical (not hardlinked) copy in another venv
+    pub duplicated: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize)]
+pub struct DiskUsage {
+    pub venvs: Vec<VenvUsage>,
+    pub total: u64,
+    /// bytes that would be freed if every duplicated file was stored only once
+    pub reclaimable: u64,
+    pub uv_cache: u64,
+    pub uv_cache_path: String,
+}
+
+pub async fn disk_usage(venv_names: &[String]) -> anyhow::Result<DiskUsage> {
+    let items = list_packages(&LoadMetadataConfig::none(), Some(venv_names), None).await?;
+
+    let mut sizes = Vec::with_capacity(items.len());
+    let mut files_per_venv = Vec::with_capacity(items.len());
+    for metadata in &items {
+        let root = venv_path(&metadata.name);
+        sizes.push(dir_size(&root));
+
+        files_per_venv.push(unshared_files(&root));
+    }
+
+    let mut copies: HashMap<&str, u64> = HashMap::new();
+    for file in files_per_venv.iter().flatten() {
+        *copies.entry(&file.hash).or_default() += 1;
+    }
+
+    let mut venvs = Vec::with_capacity(items.len());
+    for ((metadata, size), files) in items.iter().zip(sizes).zip(&files_per_venv) {
+        let duplicated = files
+            .iter()
+            .filter(|file| {
+                copies
+                    .get(file.hash.as_str())
+                    .is_some_and(|count| *count > 1)
+            })
+            .map(|file| file.size)
+            .sum();
+
+        venvs.push(VenvUsage {
+            name: metadata.name.clone(),
+            size,
+            duplicated,
+        });
+    }
+
+    // every duplicated file needs to be kept once:
+    let mut reclaimable: u64 = 0;
+    let mut counted: HashSet<&str> = HashSet::new();
+    for file in files_per_venv.iter().flatten() {
+        if copies
+            .get(file.hash.as_str())
+            .is_some_and(|count| *count > 1)
+            && !counted.insert(&file.hash)
+        {
+            reclaimable += file.size;
+        }
+    }
+
+    let cache = uv_cache();
+    let uv_cache_path = cache.root().as_str().to_owned();
+
+    Ok(DiskUsage {
+        total: venvs.iter().map(|venv| venv.size).sum(),
+        venvs,
+        reclaimable,
+        uv_cache: dir_size(cache.root()),
+        uv_cache_path,
+    })
+}
+
+fn print_human(usage: &DiskUsage) {
+    let mut venvs: Vec<&VenvUsage> = usage.venvs.iter().collect();
+    // largest first, that's what you're looking for:
+    venvs.sort_by(|left, right| right.size.cmp(&left.size));
+
+    for venv in venvs {
+        print!("{:>10}  {}", format_size(venv.size).bold(), venv.name);
+        if venv.duplicated > 0 {
+            print!(" ({} duplicated)", format_size(venv.duplicated).yellow());
+        }
+        println!();
+    }
+
+    println!();
+    println!("{:>10}  total", format_size(usage.total).bold());
+    if usage.reclaimable > 0 {
+        println!(
+            "{:>10}  could be saved by deduplicating identical files",
+            format_size(usage.reclaimable).yellow()
+        );
+    }
+    println!(
+        "{:>10}  uv cache ({})",
+        format_size(usage.uv_cache).bold(),
+        usage.uv_cache_path.blue()
+    );
+}
+
+impl Process for DuOptions {
+    async fn process(self) -> anyhow::Result<i32> {
+        let usage = disk_usage(&self.venv_names)
+            .await
+            .with_context(|| "Something went wrong while calculating disk usage;")?;
+
+        if self.json || is_json() {
+            println!("{}", serde_json::to_string_pretty(&usage)?);
+        } else {
+            print_human(&usage);
+        }
+
+        Ok(0)
+    }
+}
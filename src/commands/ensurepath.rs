@@ -1,89 +1,149 @@
 use anyhow::Context;
-use std::path::PathBuf;
+use std::io::{self, Write};
+use std::path::Path;
 
-use chrono::Local;
-use core::fmt::Write;
 use owo_colors::OwoColorize;
-use tokio::{fs::OpenOptions, io::AsyncWriteExt};
 
 use crate::helpers::PathAsStr;
 use crate::{
     cli::{EnsurepathOptions, Process},
-    metadata::{ensure_bin_dir, get_home_dir},
+    metadata::ensure_bin_dir,
+    shells::{read_rc_block, remove_rc_block, upsert_rc_block, SupportedShell},
 };
 
-pub fn now() -> String {
-    let dt = Local::now();
-
-    match dt.to_string().split_once('.') {
-        None => String::new(),
-        Some((datetime, _)) => datetime.to_owned(),
+async fn read_rc(path: &Path) -> anyhow::Result<String> {
+    match tokio::fs::read_to_string(path).await {
+        Ok(content) => Ok(content),
+        // e.g. no ~/.config/fish/conf.d/uvenv.fish yet
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(String::new()),
+        Err(err) => Err(err.into()),
     }
 }
 
-pub async fn append(
-    file_path: &PathBuf,
-    text: &str,
+/// Write to a temporary file next to the rc file and rename it over the original,
+/// so an interrupted write can't leave a truncated rc file behind.
+async fn write_rc(
+    path: &Path,
+    content: &str,
 ) -> anyhow::Result<()> {
-    let mut file = OpenOptions::new().append(true).open(file_path).await?;
-
-    file.write_all(text.as_bytes()).await?;
+    // rc files are often symlinks into a dotfiles repo, replace the file instead of the link:
+    let path = tokio::fs::canonicalize(path)
+        .await
+        .unwrap_or_else(|_| path.to_path_buf());
+    let parent = path.parent().unwrap_or_else(|| Path::new("."));
+    tokio::fs::create_dir_all(parent).await?;
+
+    let mut temp = tempfile::Builder::new()
+        .prefix(".uvenv-")
+        .tempfile_in(parent)?;
+    temp.write_all(content.as_bytes())?;
+    if let Ok(metadata) = tokio::fs::metadata(&path).await {
+        // e.g. keep a read-only rc file read-only
+        temp.as_file().set_permissions(metadata.permissions())?;
+    }
+    temp.persist(&path)?;
 
     Ok(())
 }
 
-pub async fn add_to_bashrc(
-    text: &str,
-    with_comment: bool,
+/// Remove an rc file that belongs to uvenv alone (if it exists).
+async fn remove_rc(path: &Path) -> anyhow::Result<()> {
+    match tokio::fs::remove_file(path).await {
+        Ok(()) => Ok(()),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(()),
+        Err(err) => Err(err.into()),
+    }
+}
+
+/// Add `line` to uvenv's block in the rc file of `shell` (if it's not in there yet).
+pub async fn add_to_rc(
+    shell: SupportedShell,
+    line: &str,
 ) -> anyhow::Result<()> {
-    let path = get_home_dir().join(".bashrc");
+    let path = shell.rc_file();
+    let content = read_rc(&path).await?;
 
-    let now = now();
-    let mut final_text = String::from("\n");
-    if with_comment {
-        // final_text.push_str(&format!("# Added by `uvenv` at {now}\n"));
-        writeln!(final_text, "# Added by `uvenv` at {now}")?;
+    let mut lines =
+        read_rc_block(&content).with_context(|| format!("Trying to read {}", path.as_str()))?;
+    if !lines.iter().any(|known| known == line) {
+        lines.push(line.to_owned());
     }
 
-    final_text.push_str(text);
-    final_text.push('\n');
-
-    append(&path, &final_text)
+    let updated = upsert_rc_block(&content, &lines)
+        .with_context(|| format!("Trying to update {}", path.as_str()))?;
+    write_rc(&path, &updated)
         .await
-        .with_context(|| "Trying to append text to your .bashrc")
+        .with_context(|| format!("Trying to update {}", path.as_str()))
 }
 
-pub async fn ensure_path(force: bool) -> anyhow::Result<i32> {
+/// Remove uvenv's block from the rc file of `shell`.
+pub async fn remove_from_rc(shell: SupportedShell) -> anyhow::Result<()> {
+    let path = shell.rc_file();
+    let content = read_rc(&path).await?;
+
+    let updated =
+        remove_rc_block(&content).with_context(|| format!("Trying to update {}", path.as_str()))?;
+
+    let result = if updated.trim().is_empty() && shell.owns_rc_file() {
+        // don't leave an empty ~/.config/fish/conf.d/uvenv.fish behind
+        remove_rc(&path).await
+    } else {
+        write_rc(&path, &updated).await
+    };
+    result.with_context(|| format!("Trying to update {}", path.as_str()))
+}
+
+/// The lines uvenv currently manages in the rc file of `shell`.
+pub async fn read_from_rc(shell: SupportedShell) -> anyhow::Result<Vec<String>> {
+    let path = shell.rc_file();
+    let content = read_rc(&path).await?;
+
+    read_rc_block(&content).with_context(|| format!("Trying to read {}", path.as_str()))
+}
+
+pub async fn ensure_path(
+    shell: SupportedShell,
+    force: bool,
+) -> anyhow::Result<i32> {
     let bin_path = ensure_bin_dir().await;
     let bin_dir = bin_path.as_str();
 
     let path = std::env::var("PATH").unwrap_or_default();
 
     if !force && path.split(':').any(|x| x == bin_dir) {
-        eprintln!("{}: {} is already added to your path. Use '--force' to add it to your .bashrc file anyway.",
-                "Warning".yellow(),
-                bin_dir.green()
+        eprintln!(
+            "{}: {} is already added to your path. Use '--force' to add it to {} anyway.",
+            "Warning".yellow(),
+            bin_dir.green(),
+            shell.rc_file().as_str()
         );
         // don't bail/Err because it's just a warning.
         // still exit with code > 0
         Ok(2) // missing -f
     } else {
-        add_to_bashrc(&format!("export PATH=\"$PATH:{bin_dir}\""), true).await?;
+        add_to_rc(shell, &shell.path_snippet(bin_dir)).await?;
 
-        println!("Added '{}' to ~/.bashrc", bin_dir.green());
+        println!(
+            "Added '{}' to {}",
+            bin_dir.green(),
+            shell.rc_file().as_str()
+        );
         Ok(0)
     }
 }
 
-pub async fn ensure_path_generate() -> String {
+pub async fn ensure_path_generate(shell: SupportedShell) -> String {
     let bin_path = ensure_bin_dir().await;
-    let bin_dir = bin_path.as_str();
-    format!("export PATH=\"$PATH:{bin_dir}\"")
+    shell.path_snippet(bin_path.as_str())
 }
 
 impl Process for EnsurepathOptions {
     async fn process(self) -> anyhow::Result<i32> {
-        if let Err(msg) = ensure_path(self.force).await {
+        let Some(shell) = SupportedShell::detect_or_warn() else {
+            return Ok(126);
+        };
+
+        if let Err(msg) = ensure_path(shell, self.force).await {
             Err(msg).with_context(|| "Something went wrong trying to ensure a proper PATH;")
         } else {
             Ok(0)
@@ -0,0 +1,34 @@
+use anyhow::{bail, Context};
+
+use crate::cli::{ExecOptions, Process};
+use crate::cmd::exec_replace;
+use crate::venv::{export_venv_environ, setup_environ_from_requirement};
+
+pub async fn exec_in_venv(
+    venv_name: &str,
+    command: &[String],
+) -> anyhow::Result<i32> {
+    let (_, environ) = setup_environ_from_requirement(venv_name).await?;
+
+    let Some((program, args)) = command.split_first() else {
+        bail!("No command to run.");
+    };
+
+    export_venv_environ(&environ);
+
+    // PATH now starts with the venv's bin, so `program` is looked up there first:
+    exec_replace(program, args)
+}
+
+impl Process for ExecOptions {
+    async fn process(self) -> anyhow::Result<i32> {
+        exec_in_venv(&self.venv, &self.command)
+            .await
+            .with_context(|| {
+                format!(
+                    "Something went wrong trying to run {:?} in '{}';",
+                    &self.command, &self.venv
+                )
+            })
+    }
+}
@@ -0,0 +1,71 @@
+use anyhow::Context;
+use owo_colors::OwoColorize;
+use std::path::PathBuf;
+
+use crate::cli::{GcOptions, Process};
+use crate::helpers::PathAsStr;
+use crate::metadata::find_orphaned_venv_dirs;
+use crate::run_cache::{find_expired_run_envs, find_stale_run_envs, remove_stale_run_env};
+use crate::uv::uv_with_output;
+
+/// Remove run environments left behind by crashed or interrupted runs or that have expired,
+/// and venv directories that don't contain a venv (anymore).
+pub async fn gc(dry_run: bool) -> anyhow::Result<Vec<PathBuf>> {
+    let mut removed = Vec::new();
+
+    let mut run_envs = find_stale_run_envs();
+    run_envs.extend(find_expired_run_envs().await);
+
+    for venv in run_envs {
+        if dry_run || remove_stale_run_env(&venv).await? {
+            removed.push(venv);
+        }
+    }
+
+    for dir in find_orphaned_venv_dirs() {
+        if !dry_run {
+            tokio::fs::remove_dir_all(&dir).await?;
+        }
+        removed.push(dir);
+    }
+
+    Ok(removed)
+}
+
+/// Remove unused entries from uv's cache (e.g. of packages that are no longer installed anywhere).
+async fn prune_uv_cache(dry_run: bool) -> anyhow::Result<i32> {
+    if dry_run {
+        println!("Would run {}", "uv cache prune".green());
+        return Ok(0);
+    }
+
+    uv_with_output(&["cache", "prune"]).await
+}
+
+impl Process for GcOptions {
+    async fn process(self) -> anyhow::Result<i32> {
+        let removed = gc(self.dry_run)
+            .await
+            .with_context(|| "Something went wrong while cleaning up;")?;
+
+        if removed.is_empty() {
+            println!("🌟 No leftover venvs to clean up.");
+        }
+
+        for venv in &removed {
+            if self.dry_run {
+                println!("Would remove {}", venv.as_str().yellow());
+            } else {
+                println!("🗑️  {} removed!", venv.as_str().yellow());
+            }
+        }
+
+        if self.keep_uv_cache {
+            return Ok(0);
+        }
+
+        prune_uv_cache(self.dry_run)
+            .await
+            .with_context(|| "Something went wrong while pruning the uv cache;")
+    }
+}
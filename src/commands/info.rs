@@ -0,0 +1,306 @@
+use anyhow::Context;
+use core::fmt::Write;
+use core::str::FromStr;
+use itertools::Itertools;
+use owo_colors::OwoColorize;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use uv_distribution_types::InstalledDist;
+use uv_installer::SitePackages;
+use uv_pep440::{Version, VersionSpecifiers};
+use uv_pep508::{ExtraName, PackageName};
+
+use crate::cli::{InfoOptions, Process};
+use crate::commands::licenses::parse_metadata_headers;
+use crate::commands::list::list_packages;
+use crate::commands::tree::injected_names;
+use crate::errors::UvenvError;
+use crate::helpers::{dir_size, format_size, PathAsStr};
+use crate::metadata::{venv_path, LoadMetadataConfig, Metadata};
+use crate::output::is_json;
+use crate::pypi::get_versions_for_packagename;
+use crate::symlinks::console_scripts;
+use crate::uv::{environment_from_root, uv_installed_packages};
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize)]
+pub struct InjectedPackage {
+    pub name: String,
+    /// empty if it's not installed (anymore)
+    pub version: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize)]
+pub struct ToolInfo {
+    pub name: String,
+    pub install_spec: String,
+    /// from `direct_url.json` for url/path installs, otherwise the package index
+    pub source: String,
+    pub editable: bool,
+    pub requested_version: String,
+    pub installed_version: String,
+    /// newer versions that satisfy the requested version
+    pub available_versions: Vec<String>,
+    pub python: String,
+    pub python_executable: String,
+    pub venv: String,
+    pub size: u64,
+    pub scripts: Vec<String>,
+    /// declared by the package but not linked (e.g. added by `uvenv runpip install --upgrade`)
+    pub hidden_scripts: Vec<String>,
+    pub injected: Vec<InjectedPackage>,
+    pub dependencies: Vec<String>,
+    pub requires_python: String,
+    pub project_urls: BTreeMap<String, String>,
+}
+
+const DEFAULT_PACKAGE_INDEX: &str = "https://pypi.org/simple";
+
+/// The index `uv` installs from: the configured one (e.g. `UV_INDEX_URL`) or PyPI.
+fn package_index() -> String {
+    ["UV_DEFAULT_INDEX", "UV_INDEX_URL"]
+        .iter()
+        .find_map(|key| std::env::var(key).ok().filter(|url| !url.is_empty()))
+        .unwrap_or_else(|| DEFAULT_PACKAGE_INDEX.to_owned())
+}
+
+/// PEP 610: packages installed from a url or path have a `direct_url.json` in their dist-info.
+pub fn direct_url(dist: &InstalledDist) -> Option<String> {
+    let content = std::fs::read_to_string(dist.path().join("direct_url.json")).ok()?;
+    let json: serde_json::Value = serde_json::from_str(&content).ok()?;
+
+    json.get("url")?.as_str().map(ToOwned::to_owned)
+}
+
+/// `Project-URL: Label, url` headers (and the older `Home-page`).
+fn project_urls(headers: &[(String, String)]) -> BTreeMap<String, String> {
+    headers
+        .iter()
+        .filter_map(|(key, value)| {
+            if key.eq_ignore_ascii_case("Project-URL") {
+                value
+                    .split_once(',')
+                    .map(|(label, url)| (label.trim().to_owned(), url.trim().to_owned()))
+            } else if key.eq_ignore_ascii_case("Home-page") {
+                Some((String::from("Homepage"), value.clone()))
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+fn header(
+    headers: &[(String, String)],
+    name: &str,
+) -> String {
+    headers
+        .iter()
+        .find(|(key, _)| key.eq_ignore_ascii_case(name))
+        .map(|(_, value)| value.clone())
+        .unwrap_or_default()
+}
+
+async fn available_versions(metadata: &Metadata) -> Vec<String> {
+    let Ok(name) = PackageName::from_str(&metadata.name) else {
+        return Vec::new();
+    };
+    // e.g. '>=1,<2' (multiple specifiers), empty if there is no constraint:
+    let constraint = VersionSpecifiers::from_str(&metadata.requested_version).unwrap_or_default();
+    let installed = metadata.installed_version_parsed();
+
+    get_versions_for_packagename(&name, true, None)
+        .await
+        .into_iter()
+        .filter(|version: &Version| version > &installed && constraint.contains(version))
+        .sorted()
+        .map(|version| version.to_string())
+        .collect()
+}
+
+pub async fn tool_info(venv_name: &str) -> anyhow::Result<ToolInfo> {
+    let filter = [venv_name.to_owned()];
+    let Some(metadata) = list_packages(&LoadMetadataConfig::default(), Some(&filter), None)
+        .await?
+        .into_iter()
+        .next()
+    else {
+        return Err(UvenvError::VenvNotFound(venv_name.to_owned()).into());
+    };
+
+    let root = venv_path(&metadata.name);
+    let environ = environment_from_root(&root)?;
+    let site_packages = SitePackages::from_environment(&environ)?;
+    let packages = uv_installed_packages(&environ)?;
+    let name = PackageName::from_str(&metadata.name)?;
+
+    let mut info = ToolInfo {
+        name: metadata.name.clone(),
+        install_spec: metadata.install_spec.clone(),
+        source: package_index(),
+        editable: metadata.editable,
+        requested_version: metadata.requested_version.clone(),
+        installed_version: metadata.installed_version.clone(),
+        available_versions: available_versions(&metadata).await,
+        python: metadata.python.clone(),
+        python_executable: environ.interpreter().sys_executable().as_str().to_owned(),
+        venv: root.as_str().to_owned(),
+        size: dir_size(&root),
+        scripts: metadata.scripts.keys().cloned().collect(),
+        injected: injected_names(&metadata)
+            .into_iter()
+            .map(|injected| InjectedPackage {
+                version: packages
+                    .get(&injected)
+                    .map(|package| package.version.clone())
+                    .unwrap_or_default(),
+                name: injected.to_string(),
+            })
+            .collect(),
+        ..Default::default()
+    };
+
+    if let Some(dist) = site_packages.get_packages(&name).first() {
+        if let Some(url) = direct_url(dist) {
+            info.source = url;
+        }
+
+        let entry_points = dist.path().join("entry_points.txt");
+        info.hidden_scripts = console_scripts(entry_points.as_str())
+            .await
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|script| !metadata.scripts.contains_key(script))
+            .sorted()
+            .collect();
+
+        if let Ok(content) = std::fs::read_to_string(dist.path().join("METADATA")) {
+            let headers = parse_metadata_headers(&content);
+            info.requires_python = header(&headers, "Requires-Python");
+            info.project_urls = project_urls(&headers);
+        }
+    }
+
+    if let Some(package) = packages.get(&name) {
+        let extras: Vec<ExtraName> = metadata
+            .extras
+            .iter()
+            .filter_map(|extra| ExtraName::from_str(extra).ok())
+            .collect();
+        let markers = environ.interpreter().markers();
+
+        info.dependencies = package
+            .requires_dist
+            .iter()
+            .filter(|requirement| requirement.evaluate_markers(markers, &extras))
+            .map(|requirement| {
+                // markers are already evaluated, so leave them out:
+                let mut requirement = requirement.clone();
+                requirement.marker = uv_pep508::MarkerTree::TRUE;
+                requirement.to_string()
+            })
+            .sorted()
+            .collect();
+    }
+
+    Ok(info)
+}
+
+fn or_dash(value: &str) -> &str {
+    if value.is_empty() {
+        "-"
+    } else {
+        value
+    }
+}
+
+impl ToolInfo {
+    pub fn format_human(&self) -> anyhow::Result<String> {
+        let mut result = String::new();
+        let list = |items: &[String]| {
+            if items.is_empty() {
+                String::from("-")
+            } else {
+                items.join(", ")
+            }
+        };
+
+        writeln!(result, "{}", self.name.bold().underline())?;
+        writeln!(result, "Install spec:      {}", self.install_spec.cyan())?;
+        writeln!(result, "Source:            {}", self.source)?;
+        if self.editable {
+            writeln!(result, "Editable:          {}", "yes".yellow())?;
+        }
+        writeln!(
+            result,
+            "Requested version: {}",
+            or_dash(&self.requested_version)
+        )?;
+        writeln!(
+            result,
+            "Installed version: {}",
+            self.installed_version.green()
+        )?;
+        writeln!(
+            result,
+            "Available:         {}",
+            list(&self.available_versions)
+        )?;
+        writeln!(
+            result,
+            "Python:            {} ({})",
+            self.python.bright_blue(),
+            self.python_executable
+        )?;
+        writeln!(
+            result,
+            "Requires Python:   {}",
+            or_dash(&self.requires_python)
+        )?;
+        writeln!(
+            result,
+            "Venv:              {} ({})",
+            self.venv.blue(),
+            format_size(self.size)
+        )?;
+        writeln!(result, "Scripts:           {}", list(&self.scripts))?;
+        if !self.hidden_scripts.is_empty() {
+            writeln!(result, "Hidden scripts:    {}", list(&self.hidden_scripts))?;
+        }
+
+        let injected: Vec<String> = self
+            .injected
+            .iter()
+            .map(|package| format!("{} {}", package.name, or_dash(&package.version)))
+            .collect();
+        writeln!(result, "Injected:          {}", list(&injected))?;
+        writeln!(result, "Dependencies:      {}", list(&self.dependencies))?;
+
+        if !self.project_urls.is_empty() {
+            writeln!(result, "Project URLs:")?;
+            for (label, url) in &self.project_urls {
+                writeln!(result, "  - {label}: {}", url.blue())?;
+            }
+        }
+
+        Ok(result)
+    }
+}
+
+impl Process for InfoOptions {
+    async fn process(self) -> anyhow::Result<i32> {
+        let info = tool_info(&self.venv_name).await.with_context(|| {
+            format!(
+                "Something went wrong trying to show info about '{}';",
+                self.venv_name
+            )
+        })?;
+
+        if self.json || is_json() {
+            println!("{}", serde_json::to_string_pretty(&info)?);
+        } else {
+            print!("{}", info.format_human()?);
+        }
+
+        Ok(0)
+    }
+}
@@ -1,4 +1,6 @@
+use crate::commands::install::sync_symlinks;
 use crate::metadata::LoadMetadataConfig;
+use crate::output::{Action, ActionResult};
 use crate::{
     animate::{show_loading_indicator, AnimationSettings},
     cli::{InjectOptions, Process},
@@ -14,9 +16,9 @@ pub async fn inject_package<S: AsRef<str> + Display>(
     venv_spec: &str,
     to_inject_specs: &[S],
     no_cache: bool,
-) -> anyhow::Result<String> {
+) -> anyhow::Result<ActionResult> {
     let (requirement, environ) = setup_environ_from_requirement(venv_spec).await?;
-    let mut metadata = Metadata::for_requirement(&requirement, &LoadMetadataConfig::none()).await;
+    let mut metadata = Metadata::for_requirement(&requirement, &LoadMetadataConfig::none()).await?;
 
     let mut args = vec!["pip", "install"];
 
@@ -44,20 +46,27 @@ pub async fn inject_package<S: AsRef<str> + Display>(
         .extend(to_inject_specs_vec.iter().map(ToString::to_string));
 
     metadata.save(&environ.to_path_buf()).await?;
+    let changes = sync_symlinks(&mut metadata, &environ, &requirement).await?;
 
-    Ok(format!(
-        "💉 Injected [{}] into {}.",
-        &to_inject_str,
-        &metadata.name.green(),
-    ))
+    let mut result = ActionResult::new(Action::Inject, &metadata.name)
+        .with_message(format!(
+            "💉 Injected [{}] into {}.",
+            &to_inject_str,
+            &metadata.name.green(),
+        ))
+        .with_changes(&changes);
+    result.old_version.clone_from(&metadata.installed_version);
+    result.new_version.clone_from(&metadata.installed_version);
+
+    Ok(result)
 }
 
 impl Process for InjectOptions {
     async fn process(self) -> anyhow::Result<i32> {
         // vec<string> -> vec<str>
         match inject_package(&self.into, &self.package_specs, self.no_cache).await {
-            Ok(msg) => {
-                println!("{msg}");
+            Ok(result) => {
+                result.print()?;
                 Ok(0)
             },
             Err(msg) => Err(msg).with_context(|| {
@@ -1,9 +1,14 @@
 use crate::animate::{show_loading_indicator, AnimationSettings};
 use crate::cli::{InstallOptions, Process};
+use crate::errors::UvenvError;
 
 use crate::metadata::Metadata;
+use crate::output::{Action, ActionResult};
 use crate::pip::parse_requirement;
-use crate::symlinks::{create_symlink, find_symlinks};
+use crate::symlinks::{
+    check_symlink, create_symlink, find_script_conflict, find_symlinks, remove_symlink,
+    ScriptChanges,
+};
 use crate::uv::{uv, uv_get_installed_version, ExtractInfo, Helpers};
 use crate::venv::{activate_venv, create_venv, remove_venv};
 
@@ -50,6 +55,29 @@ pub async fn _install_package<S: AsRef<str>>(
     .await
 }
 
+/// Install a list of requirements (e.g. the dependencies of a script) into the active venv.
+pub async fn _install_requirements<S: AsRef<str>>(
+    requirements: &[S],
+    no_cache: bool,
+) -> anyhow::Result<bool> {
+    let mut args: Vec<&str> = vec!["pip", "install"];
+
+    if no_cache {
+        args.push("--no-cache");
+    }
+
+    args.extend(requirements.iter().map(AsRef::as_ref));
+
+    let promise = uv(&args);
+
+    show_loading_indicator(
+        promise,
+        "installing dependencies",
+        AnimationSettings::default(),
+    )
+    .await
+}
+
 async fn ensure_venv(
     maybe_venv: Option<&Path>,
     requirement: &Requirement,
@@ -109,7 +137,6 @@ pub async fn install_symlinks(
     venv: &PythonEnvironment,
     requirement: &Requirement,
     force: bool,
-    binaries: &[&str],
 ) -> anyhow::Result<()> {
     let venv_root = venv.root();
 
@@ -117,7 +144,13 @@ pub async fn install_symlinks(
 
     let mut results = BTreeMap::new();
     for symlink in symlinks {
-        let result = create_symlink(&symlink, venv_root, force, binaries).await;
+        if check_symlink(&symlink, venv_root).await {
+            // already linked to this venv (e.g. `check --fix`)
+            results.insert(symlink, true);
+            continue;
+        }
+
+        let result = create_symlink(&symlink, venv_root, force).await;
 
         let success = result.unwrap_or_else(|msg| {
             eprintln!("⚠️ {}", msg.yellow());
@@ -133,6 +166,67 @@ pub async fn install_symlinks(
     Ok(())
 }
 
+/// Re-read the entry points of the currently installed version and bring the bin directory in line:
+/// new scripts are linked, scripts that are no longer provided are unlinked.
+pub async fn sync_symlinks(
+    meta: &mut Metadata,
+    venv: &PythonEnvironment,
+    requirement: &Requirement,
+) -> anyhow::Result<ScriptChanges> {
+    let mut changes = ScriptChanges::default();
+
+    if meta.install_spec.is_empty() {
+        // bare venv (`uvenv create`), nothing to link
+        return Ok(changes);
+    }
+
+    let venv_root = venv.root();
+
+    if let Ok(version) = uv_get_installed_version(&requirement.name, Some(venv)) {
+        meta.installed_version = version;
+    }
+
+    let symlinks = find_symlinks(requirement, &meta.installed_version, venv).await;
+
+    for script in meta.scripts.keys() {
+        if symlinks.contains(script) {
+            continue;
+        }
+
+        // only remove links that actually point into this venv:
+        if check_symlink(script, venv_root).await {
+            remove_symlink(script).await?;
+            changes.unlinked.push(script.to_owned());
+        }
+    }
+
+    let mut results = BTreeMap::new();
+    for symlink in symlinks {
+        let success = if meta.scripts.contains_key(&symlink) {
+            check_symlink(&symlink, venv_root).await
+        } else {
+            let result = create_symlink(&symlink, venv_root, false).await;
+
+            let success = result.unwrap_or_else(|msg| {
+                eprintln!("⚠️ {}", msg.yellow());
+                false
+            });
+
+            if success {
+                changes.linked.push(symlink.clone());
+            }
+            success
+        };
+
+        results.insert(symlink, success);
+    }
+
+    meta.scripts = results;
+    meta.save(venv_root).await?;
+
+    Ok(changes)
+}
+
 pub async fn install_package<S: AsRef<str> + Display>(
     install_spec: &str,
     maybe_venv: Option<&Path>,
@@ -141,7 +235,7 @@ pub async fn install_package<S: AsRef<str> + Display>(
     inject: &[S],
     no_cache: bool,
     editable: bool,
-) -> anyhow::Result<String> {
+) -> anyhow::Result<ActionResult> {
     let (requirement, resolved_install_spec) = parse_requirement(install_spec).await?;
 
     let venv_path = ensure_venv(maybe_venv, &requirement, python, force).await?;
@@ -164,13 +258,34 @@ pub async fn install_package<S: AsRef<str> + Display>(
     )
     .await?;
 
-    install_symlinks(&mut metadata, &uv_venv, &requirement, force, &[]).await?;
+    if !force {
+        // refuse before linking anything, so a conflict doesn't leave half of the scripts linked:
+        let symlinks = find_symlinks(&requirement, &metadata.installed_version, &uv_venv).await;
+        if let Some(conflict) = find_script_conflict(&symlinks, uv_venv.root()).await {
+            let _ = remove_venv(&venv_path).await;
 
-    Ok(format!(
+            return Err(UvenvError::ScriptConflict(conflict).into());
+        }
+    }
+
+    install_symlinks(&mut metadata, &uv_venv, &requirement, force).await?;
+
+    let mut result = ActionResult::new(Action::Install, &requirement_name).with_message(format!(
         "📦 {} ({}) installed!",
         requirement_name,
         metadata.installed_version.cyan()
-    )) // :package:
+    )); // :package:
+
+    result.new_version.clone_from(&metadata.installed_version);
+    for (script, linked) in &metadata.scripts {
+        if *linked {
+            result.scripts_linked.push(script.clone());
+        } else {
+            result.warn(format!("Script '{script}' could not be linked."));
+        }
+    }
+
+    Ok(result)
 }
 
 impl Process for InstallOptions {
@@ -186,8 +301,8 @@ impl Process for InstallOptions {
         )
         .await
         {
-            Ok(msg) => {
-                println!("{msg}");
+            Ok(result) => {
+                result.print()?;
                 Ok(0)
             },
             Err(msg) => Err(msg).with_context(|| {
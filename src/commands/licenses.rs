@@ -0,0 +1,197 @@
+use anyhow::Context;
+use itertools::Itertools;
+use owo_colors::OwoColorize;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use uv_distribution_types::{InstalledDist, Name};
+use uv_installer::SitePackages;
+
+use crate::cli::{LicensesOptions, Process};
+use crate::commands::list::list_packages;
+use crate::config::load_config;
+use crate::metadata::{venv_path, LoadMetadataConfig};
+use crate::output::is_json;
+use crate::uv::environment_from_root;
+
+const UNKNOWN_LICENSE: &str = "UNKNOWN";
+const LICENSE_CLASSIFIER: &str = "License :: ";
+
+/// Parse the (email-style) header of a METADATA / PKG-INFO file.
+/// Indented lines continue the previous header (e.g. a full license text in `License:`).
+pub fn parse_metadata_headers(content: &str) -> Vec<(String, String)> {
+    let mut headers: Vec<(String, String)> = Vec::new();
+
+    for line in content.lines() {
+        if line.trim().is_empty() {
+            // the body (long description) starts after the first empty line
+            break;
+        }
+
+        if line.starts_with([' ', '\t']) {
+            if let Some((_, value)) = headers.last_mut() {
+                value.push('\n');
+                value.push_str(line.trim());
+            }
+        } else if let Some((key, value)) = line.split_once(':') {
+            headers.push((key.trim().to_owned(), value.trim().to_owned()));
+        }
+    }
+
+    headers
+}
+
+/// Determine the license of a distribution from its metadata headers:
+/// `License-Expression` (PEP 639) > `License` > `License ::` classifiers.
+pub fn detect_license(headers: &[(String, String)]) -> String {
+    let header = |name: &str| {
+        headers
+            .iter()
+            .find(|(key, value)| key.eq_ignore_ascii_case(name) && !value.is_empty())
+            .map(|(_, value)| value.as_str())
+    };
+
+    if let Some(expression) = header("License-Expression") {
+        return expression.to_owned();
+    }
+
+    // some packages put the full license text in `License`, which is useless in an overview:
+    if let Some(license) = header("License")
+        .filter(|license| !license.contains('\n') && !license.eq_ignore_ascii_case(UNKNOWN_LICENSE))
+    {
+        return license.to_owned();
+    }
+
+    let classifiers = headers
+        .iter()
+        .filter(|(key, _)| key.eq_ignore_ascii_case("Classifier"))
+        .filter_map(|(_, value)| value.strip_prefix(LICENSE_CLASSIFIER))
+        // e.g. 'OSI Approved :: MIT License' -> 'MIT License'
+        .filter_map(|value| value.rsplit(" :: ").next())
+        .filter(|value| *value != "OSI Approved")
+        .unique()
+        .join(", ");
+
+    if classifiers.is_empty() {
+        UNKNOWN_LICENSE.to_owned()
+    } else {
+        classifiers
+    }
+}
+
+fn read_license(dist: &InstalledDist) -> String {
+    let path = dist.path();
+    // .dist-info has METADATA, .egg-info has PKG-INFO:
+    let content = ["METADATA", "PKG-INFO"]
+        .iter()
+        .find_map(|filename| std::fs::read_to_string(path.join(filename)).ok());
+
+    content.map_or_else(
+        || UNKNOWN_LICENSE.to_owned(),
+        |metadata| detect_license(&parse_metadata_headers(&metadata)),
+    )
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize)]
+pub struct PackageLicense {
+    pub license: String,
+    pub package: String,
+    pub version: String,
+    pub venv: String,
+    pub denied: bool,
+}
+
+fn is_denied(
+    license: &str,
+    denylist: &[String],
+) -> bool {
+    let license = license.to_lowercase();
+    denylist
+        .iter()
+        .any(|denied| license.contains(&denied.to_lowercase()))
+}
+
+fn venv_licenses(
+    venv_name: &str,
+    denylist: &[String],
+) -> anyhow::Result<Vec<PackageLicense>> {
+    let environ = environment_from_root(&venv_path(venv_name))?;
+    let site_packages = SitePackages::from_environment(&environ)?;
+
+    Ok(site_packages
+        .iter()
+        .map(|dist| {
+            let license = read_license(dist);
+            PackageLicense {
+                denied: is_denied(&license, denylist),
+                license,
+                package: dist.name().to_string(),
+                version: dist.version().to_string(),
+                venv: venv_name.to_owned(),
+            }
+        })
+        .collect())
+}
+
+pub async fn list_licenses(
+    venv_names: &[String],
+    extra_denied: &[String],
+) -> anyhow::Result<Vec<PackageLicense>> {
+    let config = load_config().await?;
+    let mut denylist = config.licenses.denylist;
+    denylist.extend_from_slice(extra_denied);
+
+    let items = list_packages(&LoadMetadataConfig::none(), Some(venv_names), None).await?;
+
+    let mut result = Vec::new();
+    for metadata in &items {
+        let licenses = venv_licenses(&metadata.name, &denylist)
+            .with_context(|| format!("Could not read the packages of '{}'", metadata.name))?;
+        result.extend(licenses);
+    }
+
+    result.sort();
+    Ok(result)
+}
+
+fn print_table(licenses: &[PackageLicense]) {
+    let by_license: BTreeMap<&str, Vec<&PackageLicense>> = licenses
+        .iter()
+        .into_group_map_by(|item| item.license.as_str())
+        .into_iter()
+        .collect();
+
+    for (license, packages) in by_license {
+        let denied = packages.iter().any(|item| item.denied);
+        if denied {
+            println!("{} {}", license.red().bold(), "(denied!)".red());
+        } else {
+            println!("{}", license.bold());
+        }
+
+        for item in packages {
+            println!(
+                "  - {} {} ({})",
+                item.package,
+                item.version.cyan(),
+                item.venv.blue()
+            );
+        }
+    }
+}
+
+impl Process for LicensesOptions {
+    async fn process(self) -> anyhow::Result<i32> {
+        let licenses = list_licenses(&self.venv_names, &self.deny)
+            .await
+            .with_context(|| "Something went wrong while collecting licenses;")?;
+
+        if self.json || is_json() {
+            println!("{}", serde_json::to_string_pretty(&licenses)?);
+        } else {
+            print_table(&licenses);
+        }
+
+        // non-zero so this can be used as a policy check:
+        Ok(i32::from(licenses.iter().any(|item| item.denied)))
+    }
+}
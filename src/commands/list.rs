@@ -1,10 +1,16 @@
+use core::fmt::Write;
+use itertools::Itertools;
 use std::fs::ReadDir;
+use std::time::SystemTime;
 
 use owo_colors::OwoColorize;
 
-use crate::cli::{ListOptions, Process};
+use crate::cli::{ListColumn, ListFormat, ListOptions, ListSort, Process};
 use crate::commands::self_version::{is_latest, uvenv_version};
-use crate::metadata::{get_venv_dir, LoadMetadataConfig, Metadata};
+use crate::commands::tree::print_dependency_trees;
+use crate::helpers::{dir_size, format_size};
+use crate::metadata::{get_venv_dir, venv_path, LoadMetadataConfig, Metadata};
+use crate::output::is_json;
 use crate::promises::handle_promises;
 use crate::pypi::get_latest_version;
 use crate::uv::uv_search_python;
@@ -32,6 +38,230 @@ async fn read_from_folder_filtered(
     handle_promises(promises).await
 }
 
+const DEFAULT_COLUMNS: [ListColumn; 4] = [
+    ListColumn::Name,
+    ListColumn::Installed,
+    ListColumn::Available,
+    ListColumn::Python,
+];
+
+/// A single value in `list --format` output.
+enum Cell {
+    Text(String),
+    List(Vec<String>),
+    Bytes(u64),
+}
+
+impl Cell {
+    fn new(
+        meta: &Metadata,
+        column: ListColumn,
+    ) -> Self {
+        match column {
+            ListColumn::Name => Self::Text(meta.name.clone()),
+            ListColumn::Installed => Self::Text(meta.installed_version.clone()),
+            ListColumn::Available => Self::Text(meta.available_version.clone()),
+            ListColumn::Python => Self::Text(meta.python.clone()),
+            ListColumn::Scripts => Self::List(meta.scripts.keys().cloned().collect()),
+            ListColumn::Injected => Self::List(meta.injected.iter().sorted().cloned().collect()),
+            // not in the metadata, so only calculated when requested:
+            ListColumn::Size => Self::Bytes(dir_size(&venv_path(&meta.name))),
+        }
+    }
+
+    fn to_text(
+        &self,
+        human: bool,
+    ) -> String {
+        match self {
+            Self::Text(text) => text.clone(),
+            Self::List(items) => items.join(" "),
+            Self::Bytes(bytes) if human => format_size(*bytes),
+            Self::Bytes(bytes) => bytes.to_string(),
+        }
+    }
+
+    fn to_json(&self) -> serde_json::Value {
+        match self {
+            Self::Text(text) => serde_json::Value::from(text.as_str()),
+            Self::List(items) => serde_json::Value::from(items.clone()),
+            Self::Bytes(bytes) => serde_json::Value::from(*bytes),
+        }
+    }
+}
+
+const fn column_name(column: ListColumn) -> &'static str {
+    match column {
+        ListColumn::Name => "name",
+        ListColumn::Installed => "installed",
+        ListColumn::Available => "available",
+        ListColumn::Python => "python",
+        ListColumn::Scripts => "scripts",
+        ListColumn::Injected => "injected",
+        ListColumn::Size => "size",
+    }
+}
+
+/// When the venv was created (`pyvenv.cfg` isn't touched afterwards).
+fn installed_date(meta: &Metadata) -> Option<SystemTime> {
+    std::fs::metadata(venv_path(&meta.name).join("pyvenv.cfg"))
+        .and_then(|file_meta| file_meta.modified())
+        .ok()
+}
+
+pub fn sort_packages(
+    items: &mut [Metadata],
+    sort: ListSort,
+) {
+    match sort {
+        ListSort::Name => items.sort(),
+        ListSort::Python => items.sort_by(|left, right| {
+            left.python
+                .cmp(&right.python)
+                .then_with(|| left.name.cmp(&right.name))
+        }),
+        ListSort::InstalledDate => items.sort_by_cached_key(installed_date),
+    }
+}
+
+fn to_rows(
+    items: &[Metadata],
+    columns: &[ListColumn],
+) -> Vec<Vec<Cell>> {
+    items
+        .iter()
+        .map(|meta| {
+            columns
+                .iter()
+                .map(|column| Cell::new(meta, *column))
+                .collect()
+        })
+        .collect()
+}
+
+/// Aligned columns with a header, for humans.
+fn render_table(
+    rows: &[Vec<Cell>],
+    columns: &[ListColumn],
+) -> anyhow::Result<String> {
+    let header: Vec<String> = columns
+        .iter()
+        .map(|column| column_name(*column).to_uppercase())
+        .collect();
+    let lines: Vec<Vec<String>> = rows
+        .iter()
+        .map(|row| row.iter().map(|cell| cell.to_text(true)).collect())
+        .collect();
+
+    let widths: Vec<usize> = (0..columns.len())
+        .map(|idx| {
+            core::iter::once(&header)
+                .chain(&lines)
+                .filter_map(|line| line.get(idx))
+                .map(|text| text.chars().count())
+                .max()
+                .unwrap_or_default()
+        })
+        .collect();
+
+    let mut result = String::new();
+    for line in core::iter::once(&header).chain(&lines) {
+        let padded = line
+            .iter()
+            .zip(&widths)
+            .map(|(text, width)| format!("{text:<width$}"))
+            .join("  ");
+        writeln!(result, "{}", padded.trim_end())?;
+    }
+
+    Ok(result)
+}
+
+fn csv_escape(text: &str) -> String {
+    if text.contains([',', '"', '\n']) {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    } else {
+        text.to_owned()
+    }
+}
+
+fn tsv_escape(text: &str) -> String {
+    text.replace(['\t', '\n'], " ")
+}
+
+/// CSV or TSV (based on `separator`), for scripts.
+fn render_separated(
+    rows: &[Vec<Cell>],
+    columns: &[ListColumn],
+    separator: &str,
+) -> anyhow::Result<String> {
+    let escape = if separator == "," {
+        csv_escape
+    } else {
+        tsv_escape
+    };
+
+    let mut result = String::new();
+    writeln!(
+        result,
+        "{}",
+        columns
+            .iter()
+            .map(|column| column_name(*column))
+            .join(separator)
+    )?;
+
+    for row in rows {
+        writeln!(
+            result,
+            "{}",
+            row.iter()
+                .map(|cell| escape(&cell.to_text(false)))
+                .join(separator)
+        )?;
+    }
+
+    Ok(result)
+}
+
+/// One object per package with only the selected columns (for JSON and YAML).
+fn to_objects(
+    rows: &[Vec<Cell>],
+    columns: &[ListColumn],
+) -> Vec<serde_json::Map<String, serde_json::Value>> {
+    rows.iter()
+        .map(|row| {
+            columns
+                .iter()
+                .zip(row)
+                .map(|(column, cell)| (column_name(*column).to_owned(), cell.to_json()))
+                .collect()
+        })
+        .collect()
+}
+
+pub fn render_list(
+    items: &[Metadata],
+    format: ListFormat,
+    columns: &[ListColumn],
+) -> anyhow::Result<String> {
+    let columns = if columns.is_empty() {
+        DEFAULT_COLUMNS.as_slice()
+    } else {
+        columns
+    };
+
+    let rows = to_rows(items, columns);
+
+    match format {
+        ListFormat::Table => render_table(&rows, columns),
+        ListFormat::Csv => render_separated(&rows, columns, ","),
+        ListFormat::Tsv => render_separated(&rows, columns, "\t"),
+        ListFormat::Json => Ok(serde_json::to_string_pretty(&to_objects(&rows, columns))? + "\n"),
+        ListFormat::Yaml => Ok(serde_yaml::to_string(&to_objects(&rows, columns))?),
+    }
+}
+
 impl ListOptions {
     pub fn process_json(
         self,
@@ -54,6 +284,7 @@ impl ListOptions {
             updates_check: !self.skip_updates,
             updates_prereleases: self.show_prereleases,
             updates_ignore_constraints: self.ignore_constraints,
+            yanked_check: self.check_yanked,
         }
     }
 }
@@ -111,14 +342,31 @@ impl Process for ListOptions {
             is_uvenv_outdated(false).await;
         }
 
-        let config = self.to_metadataconfig();
+        let config = if self.tree {
+            // the tree doesn't show versions or scripts, so skip the (network) checks:
+            LoadMetadataConfig::none()
+        } else {
+            self.to_metadataconfig()
+        };
 
-        let items = list_packages(&config, Some(&self.venv_names), self.python.as_ref()).await?;
+        let mut items =
+            list_packages(&config, Some(&self.venv_names), self.python.as_ref()).await?;
+        sort_packages(&mut items, self.sort);
 
-        if self.json {
+        // `--output json` takes precedence over `--format`, and turns `--tree` into a json tree:
+        if self.tree {
+            return print_dependency_trees(&items, is_json());
+        }
+
+        if self.json || is_json() {
             return self.process_json(&items);
         }
 
+        if let Some(format) = self.format {
+            print!("{}", render_list(&items, format, &self.columns)?);
+            return Ok(0);
+        }
+
         for metadata in items {
             if self.verbose {
                 // println!("{}", dbg_pls::color(&metadata));
@@ -1,26 +1,40 @@
 pub mod activate;
+pub mod audit;
+pub mod audit_update_db;
+pub mod cache_prune_runs;
 pub mod completions;
 pub mod create;
+pub mod du;
 
 pub mod check;
 pub mod ensurepath;
+pub mod exec;
+pub mod gc;
+pub mod info;
 pub mod inject;
 pub mod install;
+pub mod licenses;
 pub mod list;
+pub mod python_list;
+pub mod python_migrate;
 pub mod reinstall;
 pub mod reinstall_all;
 pub mod run;
 pub mod runpip;
 pub mod runpython;
 pub mod runuv;
+pub mod sbom;
 pub mod self_changelog;
 pub mod self_link;
 pub mod self_migrate;
 pub mod self_update;
 pub mod self_version;
 pub mod setup;
+pub mod shell;
+pub mod tree;
 pub mod uninject;
 pub mod uninstall;
 pub mod uninstall_all;
 pub mod upgrade;
 pub mod upgrade_all;
+pub mod which;
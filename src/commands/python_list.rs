@@ -0,0 +1,99 @@
+use itertools::Itertools;
+use owo_colors::OwoColorize;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use crate::cli::{Process, PythonListOptions};
+use crate::commands::list::list_packages;
+use crate::metadata::{LoadMetadataConfig, Metadata};
+
+/// An interpreter (identified by its stdlib path) and the venvs using it.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct PythonUsage {
+    pub python: String,
+    pub python_raw: String,
+    pub venvs: Vec<String>,
+}
+
+impl PythonUsage {
+    /// The interpreter can disappear, e.g. after a system upgrade removed an old Python version.
+    pub fn exists(&self) -> bool {
+        !self.python_raw.is_empty() && Path::new(&self.python_raw).exists()
+    }
+
+    pub fn format_human(&self) -> String {
+        let python = if self.python.is_empty() {
+            "Unknown Python"
+        } else {
+            self.python.as_str()
+        };
+
+        let status = if self.exists() {
+            String::new()
+        } else {
+            format!(" {}", "(missing!)".red())
+        };
+
+        format!(
+            "🐍 {} {}{}\n    {}\n",
+            python.blue(),
+            self.python_raw.dimmed(),
+            status,
+            self.venvs.iter().map(|name| name.green()).join(", ")
+        )
+    }
+}
+
+/// Group venvs by the interpreter they were created with.
+pub fn group_by_python(items: Vec<Metadata>) -> Vec<PythonUsage> {
+    let mut grouped: BTreeMap<String, PythonUsage> = BTreeMap::new();
+
+    for meta in items {
+        let usage = grouped
+            .entry(meta.python_raw.clone())
+            .or_insert_with(|| PythonUsage {
+                python: meta.python.clone(),
+                python_raw: meta.python_raw.clone(),
+                venvs: Vec::new(),
+            });
+
+        usage.venvs.push(meta.name);
+    }
+
+    grouped
+        .into_values()
+        .map(|mut usage| {
+            usage.venvs.sort();
+            usage
+        })
+        .collect()
+}
+
+pub async fn list_pythons() -> anyhow::Result<Vec<PythonUsage>> {
+    let items = list_packages(&LoadMetadataConfig::none(), None, None).await?;
+
+    Ok(group_by_python(items))
+}
+
+impl Process for PythonListOptions {
+    async fn process(self) -> anyhow::Result<i32> {
+        let pythons = list_pythons().await?;
+
+        if pythons.is_empty() {
+            println!("🌟 No venvs installed yet.");
+        }
+
+        for usage in &pythons {
+            print!("{}", usage.format_human());
+        }
+
+        if pythons.iter().any(|usage| !usage.exists()) {
+            eprintln!(
+                "\nTip: use `{}` to move venvs to an interpreter that still exists.",
+                "uvenv python migrate --from <version> --to <version>".green()
+            );
+        }
+
+        Ok(0)
+    }
+}
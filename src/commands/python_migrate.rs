@@ -0,0 +1,177 @@
+use anyhow::{anyhow, bail, Context};
+use owo_colors::OwoColorize;
+use std::path::{Path, PathBuf};
+
+use crate::cli::{Process, PythonMigrateOptions};
+use crate::commands::create::create;
+use crate::commands::install::install_package;
+use crate::commands::list::list_packages;
+use crate::errors::UvenvError;
+use crate::metadata::{get_work_dir, venv_path, LoadMetadataConfig, Metadata};
+use crate::uv::uv_search_python;
+use crate::venv::remove_venv;
+
+/// Is `meta` using the interpreter requested with `--from`?
+fn uses_python(
+    meta: &Metadata,
+    from: &str,
+    from_stdlib: Option<&String>,
+) -> bool {
+    if from_stdlib.is_some_and(|stdlib| &meta.python_raw == stdlib) {
+        return true;
+    }
+
+    // the old interpreter may be gone already, so also compare the recorded version (e.g. 'CPython 3.11.4'):
+    meta.python
+        .split_whitespace()
+        .last()
+        .is_some_and(|version| version == from || version.starts_with(&format!("{from}.")))
+}
+
+fn staging_path(venv_name: &str) -> PathBuf {
+    get_work_dir().join("python-migrate").join(venv_name)
+}
+
+/// Reinstall one venv with a new interpreter.
+/// The original venv is moved aside first, and restored if anything goes wrong.
+async fn migrate_venv(
+    meta: &Metadata,
+    python: Option<&String>,
+    no_cache: bool,
+) -> anyhow::Result<String> {
+    let venv_dir = venv_path(&meta.name);
+    let staged = staging_path(&meta.name);
+
+    if staged.exists() {
+        bail!(
+            "A previous migration of '{}' was not finished, check {:?} first.",
+            meta.name,
+            staged
+        );
+    }
+
+    if let Some(parent) = staged.parent() {
+        tokio::fs::create_dir_all(parent).await?;
+    }
+    tokio::fs::rename(&venv_dir, &staged).await?;
+
+    let result = if meta.install_spec.is_empty() {
+        create(&meta.name, python, true, true).await
+    } else {
+        install_package(
+            &meta.install_spec,
+            None,
+            python,
+            true,
+            &meta.vec_injected(),
+            no_cache,
+            meta.editable,
+        )
+        .await
+    };
+
+    match result {
+        Ok(installed) => {
+            remove_venv(&staged).await?;
+            Ok(installed.message)
+        },
+        Err(err) => {
+            restore_venv(&venv_dir, &staged).await.with_context(|| {
+                format!("Could not restore the original venv, it can be found at {staged:?}")
+            })?;
+            Err(err.context(format!("Restored the original venv for '{}'", meta.name)))
+        },
+    }
+}
+
+async fn restore_venv(
+    venv_dir: &Path,
+    staged: &Path,
+) -> anyhow::Result<()> {
+    if venv_dir.exists() {
+        // partially created new venv
+        tokio::fs::remove_dir_all(venv_dir).await?;
+    }
+    tokio::fs::rename(staged, venv_dir).await?;
+
+    Ok(())
+}
+
+pub async fn python_migrate(
+    from: &str,
+    to: &str,
+    dry_run: bool,
+    no_cache: bool,
+    venv_names: &[String],
+) -> anyhow::Result<()> {
+    let to_python = to.to_owned();
+
+    // don't touch any venv if the new interpreter can't be found:
+    let Some(to_stdlib) = uv_search_python(Some(&to_python)).await else {
+        return Err(UvenvError::InterpreterMissing(to.to_owned()).into());
+    };
+    let from_stdlib = uv_search_python(Some(&from.to_owned())).await;
+
+    if from_stdlib.as_ref() == Some(&to_stdlib) {
+        bail!("'{from}' and '{to}' are the same Python interpreter.");
+    }
+
+    let items = list_packages(&LoadMetadataConfig::none(), Some(venv_names), None).await?;
+    let to_migrate: Vec<Metadata> = items
+        .into_iter()
+        .filter(|meta| uses_python(meta, from, from_stdlib.as_ref()))
+        .collect();
+
+    if to_migrate.is_empty() {
+        println!("🌟 No venvs use Python '{from}'.");
+        return Ok(());
+    }
+
+    if dry_run {
+        for meta in &to_migrate {
+            println!(
+                "Would migrate {} ({} -> {})",
+                meta.name.green(),
+                meta.python.red(),
+                to.blue()
+            );
+        }
+        return Ok(());
+    }
+
+    let mut all_ok = true;
+    // only used if not all_ok, but already created for chaining:
+    let mut err_result = Err(anyhow!("-> Failed python migrate."));
+
+    for meta in &to_migrate {
+        match migrate_venv(meta, Some(&to_python), no_cache).await {
+            Ok(msg) => println!("{msg}"),
+            Err(msg) => {
+                err_result = err_result.with_context(|| msg);
+                all_ok = false;
+            },
+        }
+    }
+
+    if all_ok {
+        Ok(())
+    } else {
+        err_result.with_context(|| "⚠️ Not all venvs were migrated!")
+    }
+}
+
+impl Process for PythonMigrateOptions {
+    async fn process(self) -> anyhow::Result<i32> {
+        python_migrate(
+            &self.from,
+            &self.to,
+            self.dry_run,
+            self.no_cache,
+            &self.venv_names,
+        )
+        .await
+        .with_context(|| "Something went wrong trying to migrate venvs;")?;
+
+        Ok(0)
+    }
+}
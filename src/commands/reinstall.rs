@@ -1,8 +1,10 @@
-use anyhow::{bail, Context};
+use anyhow::Context;
 use owo_colors::OwoColorize;
 
 use crate::commands::create::create;
+use crate::errors::UvenvError;
 use crate::metadata::LoadMetadataConfig;
+use crate::output::{Action, ActionResult};
 use crate::{
     cli::{Process, ReinstallOptions},
     commands::{install::install_package, uninstall::uninstall_package},
@@ -18,31 +20,33 @@ pub async fn reinstall(
     with_injected: bool,
     no_cache: bool,
     editable: bool,
-) -> anyhow::Result<String> {
+) -> anyhow::Result<ActionResult> {
     let (requirement, _resolved_install_spec) = parse_requirement(install_spec).await?;
     let requirement_name = requirement.name.to_string();
 
     let venv_dir = venv_path(&requirement_name);
 
     if !venv_dir.exists() && !force {
-        bail!("'{}' was not previously installed. Please run 'uvenv install {}' or pass `--force` instead.",
-            &requirement_name,
-            &install_spec,
+        eprintln!(
+            "💡 Please run '{}' or pass `--force` instead.",
+            format!("uvenv install {install_spec}").green()
         );
+        return Err(UvenvError::VenvNotFound(requirement_name).into());
     }
 
-    let current_metadata =
-        Metadata::for_requirement(&requirement, &LoadMetadataConfig::none()).await;
+    // a corrupt `.metadata` shouldn't prevent recreating the venv (e.g. `check --fix`):
+    let current_metadata = Metadata::for_requirement(&requirement, &LoadMetadataConfig::none())
+        .await
+        .unwrap_or_else(|_| Metadata::find(&requirement));
 
     let install_spec_changed =
         editable || !requirement.version().is_empty() || !requirement.extras().is_empty();
 
+    let mut warnings = Vec::new();
     if let Err(err) = uninstall_package(&requirement_name, force).await {
-        eprintln!(
-            "{}: something went wrong during uninstall ({});",
-            "Warning".yellow(),
-            err
-        );
+        let warning = format!("something went wrong during uninstall ({err});");
+        eprintln!("{}: {warning}", "Warning".yellow());
+        warnings.push(warning);
     }
 
     let new_install_spec = if install_spec_changed {
@@ -57,7 +61,7 @@ pub async fn reinstall(
         Vec::new()
     };
 
-    if new_install_spec.is_empty() {
+    let mut result = if new_install_spec.is_empty() {
         create(
             &current_metadata.name,
             python,
@@ -76,7 +80,17 @@ pub async fn reinstall(
             editable,
         )
         .await
+    }?;
+
+    result.action = Action::Reinstall;
+    result
+        .old_version
+        .clone_from(&current_metadata.installed_version);
+    for warning in warnings {
+        result.warn(warning);
     }
+
+    Ok(result)
 }
 
 impl Process for ReinstallOptions {
@@ -91,8 +105,8 @@ impl Process for ReinstallOptions {
         )
         .await
         {
-            Ok(msg) => {
-                println!("{msg}");
+            Ok(result) => {
+                result.print()?;
                 Ok(0)
             },
             Err(msg) => Err(msg).with_context(|| {
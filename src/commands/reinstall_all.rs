@@ -2,6 +2,7 @@ use crate::cli::{Process, ReinstallAllOptions};
 use crate::commands::list::list_packages;
 use crate::commands::reinstall::reinstall;
 use crate::metadata::LoadMetadataConfig;
+use crate::output::{is_json, print_results_json, Action, ActionResult};
 use anyhow::{anyhow, Context};
 
 pub async fn reinstall_all(
@@ -15,6 +16,7 @@ pub async fn reinstall_all(
     let mut all_ok = true;
     // only used if not all_ok, but already created for chaining:
     let mut err_result = Err(anyhow!("-> Failed reinstall-all."));
+    let mut results = Vec::new();
 
     for meta in list_packages(&LoadMetadataConfig::none(), Some(venv_names), None).await? {
         match reinstall(
@@ -27,16 +29,25 @@ pub async fn reinstall_all(
         )
         .await
         {
-            Ok(msg) => {
-                println!("{msg}");
+            Ok(result) => {
+                if !is_json() {
+                    println!("{result}");
+                }
+                results.push(result);
             },
             Err(msg) => {
+                results.push(ActionResult::failed(Action::Reinstall, &meta.name, &msg));
                 err_result = err_result.with_context(|| msg);
                 // eprintln!("{}", msg.red());
                 all_ok = false;
             },
         }
     }
+
+    if is_json() {
+        print_results_json(&results)?;
+    }
+
     if all_ok {
         Ok(())
     } else {
@@ -57,6 +68,8 @@ impl Process for ReinstallAllOptions {
         .await
         {
             Ok(()) => Ok(0),
+            // the errors are already part of the json output:
+            Err(_) if is_json() => Ok(1),
             Err(msg) => Err(msg),
         }
     }
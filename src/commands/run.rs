@@ -1,18 +1,21 @@
 use anyhow::{bail, Context};
 use owo_colors::OwoColorize;
+use std::ffi::OsStr;
 use std::path::{Path, PathBuf};
+use tokio::fs::canonicalize;
 use uv_pep508::Requirement;
 
 use uv_python::PythonEnvironment;
 
 use crate::cli::{Process, RunOptions};
-use crate::commands::install::_install_package;
-use crate::commands::runpython::process_subprocess;
+use crate::cmd::{cancel_on_signal, exec_replace};
+use crate::commands::install::{_install_package, _install_requirements};
 use crate::helpers::PathAsStr;
+use crate::pep723::{is_script, read_script_metadata};
 use crate::pip::parse_requirement;
+use crate::run_cache::{run_env_key, RunEnv, RunMetadata};
 use crate::symlinks::find_symlinks;
 use crate::uv::uv_get_installed_version;
-use crate::venv::{activate_venv, create_venv, remove_venv};
 use core::fmt::Write;
 
 async fn _find_executable(
@@ -82,66 +85,148 @@ pub async fn run_executable(
     let full_exec_path =
         find_executable(requirement, binary, package_spec, venv, venv_path).await?;
 
-    process_subprocess(full_exec_path.as_path(), args)
+    exec_replace(full_exec_path.as_path(), args)
 }
+
+#[expect(clippy::too_many_arguments, reason = "Mirrors the `run` cli options")]
 pub async fn run_package<S: AsRef<str>>(
     package_spec: &str,
     python: Option<&String>,
     keep: bool,
+    refresh: bool,
     no_cache: bool,
     binary: Option<&String>,
     inject: &[S],
     args: &[String],
 ) -> anyhow::Result<i32> {
-    // 1. create a temp venv
-    // 2. install package
+    // 1. find (or create) a cached venv for this spec
+    // 2. install package if the venv is new or expired
     // 3. run 'binary' or find runnable(s) in package
-    // 4. if not 'keep': remove temp venv
 
     // ### 1 ###
 
     let (requirement, _) = parse_requirement(package_spec).await?;
 
-    let venv_path = &create_venv(
-        &requirement.name,
-        python,
-        true,
-        true,
-        Some(String::from("/tmp/uvenv-")),
-    )
-    .await?;
+    let key = run_env_key(requirement.name.as_ref(), package_spec, python, inject);
+
+    let env = cancel_on_signal(async {
+        let env = RunEnv::open(&key, python, refresh).await?;
 
-    let venv_name = &venv_path.as_str();
+        // ### 2 ###
+        if !env.is_new {
+            return anyhow::Ok(env);
+        }
+
+        // already expects activated venv:
+        _install_package(package_spec, inject, no_cache, false, false).await?;
+
+        env.finish(&RunMetadata::new(package_spec, python, inject))
+            .await
+    })
+    .await?;
 
     if keep {
-        eprintln!("ℹ️ Using virtualenv {}", venv_name.blue());
+        eprintln!("ℹ️ Using virtualenv {}", env.path.as_str().blue());
     }
 
-    // ### 2 ###
-    let venv = &activate_venv(venv_path).await?;
+    // ### 3 ###
+    // the executable inherits the (shared) lock, so the venv isn't pruned while it runs:
+    env.keep_locked_across_exec()?;
+    run_executable(
+        &requirement,
+        binary,
+        package_spec,
+        &env.venv,
+        &env.path,
+        args,
+    )
+    .await
+}
 
-    // already expects activated venv:
-    _install_package(package_spec, inject, no_cache, false, false).await?;
+/// Run a Python script with inline metadata (PEP 723) in a cached venv with its dependencies.
+pub async fn run_script<S: AsRef<str>>(
+    script: &str,
+    python: Option<&String>,
+    keep: bool,
+    refresh: bool,
+    no_cache: bool,
+    with: &[S],
+    args: &[String],
+) -> anyhow::Result<i32> {
+    let script_path = canonicalize(script).await?;
+    let metadata = read_script_metadata(&script_path).await?;
 
-    // ### 3 ###
-    let result = run_executable(&requirement, binary, package_spec, venv, venv_path, args).await;
+    // an explicit --python wins over `requires-python`:
+    let python = python.cloned().or(metadata.requires_python);
+
+    let mut dependencies = metadata.dependencies;
+    dependencies.extend(with.iter().map(|dep| dep.as_ref().to_owned()));
+
+    let name = script_path
+        .file_stem()
+        .and_then(OsStr::to_str)
+        .unwrap_or("script");
+
+    // keyed on the dependencies (not the script itself), so editing the script keeps the venv:
+    let key = run_env_key(name, "", python.as_ref(), &dependencies);
+
+    let env = cancel_on_signal(async {
+        let env = RunEnv::open(&key, python.as_ref(), refresh).await?;
+
+        if !env.is_new {
+            return anyhow::Ok(env);
+        }
+
+        if !dependencies.is_empty() {
+            _install_requirements(&dependencies, no_cache).await?;
+        }
 
-    // ### 4 ###
+        env.finish(&RunMetadata::new(
+            script_path.as_str(),
+            python.as_ref(),
+            &dependencies,
+        ))
+        .await
+    })
+    .await?;
 
-    if !keep {
-        // defer! not possible here because of await
-        remove_venv(venv_path).await?;
+    if keep {
+        eprintln!("ℹ️ Using virtualenv {}", env.path.as_str().blue());
     }
 
-    result
+    let mut python_args = vec![script_path.as_str().to_owned()];
+    python_args.extend_from_slice(args);
+
+    env.keep_locked_across_exec()?;
+    exec_replace(env.venv.interpreter().sys_executable(), &python_args)
 }
 
 impl Process for RunOptions {
     async fn process(self) -> anyhow::Result<i32> {
+        if is_script(&self.package_name) {
+            return run_script(
+                &self.package_name,
+                self.python.as_ref(),
+                self.keep,
+                self.refresh,
+                self.no_cache,
+                &self.with,
+                &self.args,
+            )
+            .await
+            .with_context(|| {
+                format!(
+                    "Something went wrong while trying to run script '{}';",
+                    &self.package_name
+                )
+            });
+        }
+
         run_package(
             &self.package_name,
             self.python.as_ref(),
             self.keep,
+            self.refresh,
             self.no_cache,
             self.binary.as_ref(),
             &self.with,
@@ -1,6 +1,6 @@
 use crate::{
     cli::{Process, RunpipOptions},
-    cmd::run_print_output,
+    cmd::exec_replace,
     venv::{setup_environ_from_requirement, venv_script},
 };
 use anyhow::Context;
@@ -13,7 +13,7 @@ pub async fn runpip(
 
     let script = venv_script(&env, "pip");
 
-    run_print_output(script, pip_args).await
+    exec_replace(script, pip_args)
 }
 
 impl Process for RunpipOptions {
@@ -1,30 +1,11 @@
 use anyhow::Context;
-use std::ffi::OsStr;
-use std::path::Path;
-use subprocess::Exec;
 
 use crate::{
     cli::{Process, RunpythonOptions},
+    cmd::exec_replace,
     venv::setup_environ_from_requirement,
 };
 
-#[expect(
-    clippy::cast_lossless,
-    clippy::as_conversions,
-    reason = "The numbers wont be that big."
-)]
-pub fn process_subprocess<S: AsRef<OsStr>>(
-    exec_path: &Path,
-    args: &[S],
-) -> anyhow::Result<i32> {
-    Ok(match Exec::cmd(exec_path).args(args).join()? {
-        subprocess::ExitStatus::Exited(int) => int as i32,
-        subprocess::ExitStatus::Signaled(int) => int as i32,
-        subprocess::ExitStatus::Other(int) => int,
-        subprocess::ExitStatus::Undetermined => 0,
-    })
-}
-
 pub async fn run_python(
     venv_name: &str,
     python_args: &[String],
@@ -34,7 +15,7 @@ pub async fn run_python(
     let py = environ.interpreter().sys_executable();
 
     // Launch Python in interactive mode
-    process_subprocess(py, python_args)
+    exec_replace(py, python_args)
 }
 
 impl Process for RunpythonOptions {
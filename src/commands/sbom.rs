@@ -0,0 +1,313 @@
+use anyhow::Context;
+use chrono::{SecondsFormat, Utc};
+use core::str::FromStr;
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+use std::path::Path;
+use uv_distribution_types::Name;
+use uv_installer::SitePackages;
+use uv_pep508::PackageName;
+
+use crate::cli::{Process, SbomFormat, SbomOptions};
+use crate::commands::list::list_packages;
+use crate::commands::self_version::uvenv_version;
+use crate::helpers::PathAsStr;
+use crate::metadata::{venv_path, LoadMetadataConfig};
+use crate::uv::environment_from_root;
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct SbomPackage {
+    pub name: String,
+    pub version: String,
+    /// sha256 of the RECORD file, which itself lists the hashes of every installed file
+    pub sha256: Option<String>,
+}
+
+impl SbomPackage {
+    /// Package URL, e.g. `pkg:pypi/charset-normalizer@3.3.2`.
+    pub fn purl(&self) -> String {
+        format!("pkg:pypi/{}@{}", self.name, self.version)
+    }
+}
+
+/// A uvenv-managed tool with everything installed in its venv.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct SbomTool {
+    pub venv: String,
+    pub python_version: String,
+    pub python_executable: String,
+    /// the main package is included here too
+    pub packages: Vec<SbomPackage>,
+}
+
+impl SbomTool {
+    fn main_package(&self) -> Option<&SbomPackage> {
+        self.packages
+            .iter()
+            .find(|package| package.name == self.venv)
+    }
+
+    fn dependencies(&self) -> impl Iterator<Item = &SbomPackage> {
+        self.packages
+            .iter()
+            .filter(|package| package.name != self.venv)
+    }
+}
+
+fn record_hash(dist_info: &Path) -> Option<String> {
+    let record = std::fs::read(dist_info.join("RECORD")).ok()?;
+    Some(format!("{:x}", Sha256::digest(record)))
+}
+
+fn collect_tool(venv_name: &str) -> anyhow::Result<SbomTool> {
+    let environ = environment_from_root(&venv_path(venv_name))?;
+    let site_packages = SitePackages::from_environment(&environ)?;
+    let interpreter = environ.interpreter();
+
+    let mut packages: Vec<SbomPackage> = site_packages
+        .iter()
+        .map(|dist| SbomPackage {
+            name: dist.name().to_string(),
+            version: dist.version().to_string(),
+            sha256: record_hash(dist.path()),
+        })
+        .collect();
+    packages.sort();
+
+    // PackageName is normalized, so the venv name should be too to find the main package:
+    let venv = PackageName::from_str(venv_name)
+        .map_or_else(|_| venv_name.to_owned(), |name| name.to_string());
+
+    Ok(SbomTool {
+        venv,
+        python_version: interpreter.python_version().to_string(),
+        python_executable: interpreter.sys_executable().as_str().to_owned(),
+        packages,
+    })
+}
+
+/// Refs must be unique within a document, but the same package can be installed in multiple venvs.
+fn bom_ref(
+    tool: &SbomTool,
+    name: &str,
+) -> String {
+    format!("{}/{name}", tool.venv)
+}
+
+fn cyclonedx_component(
+    tool: &SbomTool,
+    package: &SbomPackage,
+    component_type: &str,
+) -> Value {
+    let mut component = json!({
+        "type": component_type,
+        "bom-ref": bom_ref(tool, &package.name),
+        "name": package.name,
+        "version": package.version,
+        "purl": package.purl(),
+    });
+
+    if let Some(sha256) = &package.sha256 {
+        component["hashes"] = json!([{"alg": "SHA-256", "content": sha256}]);
+    }
+
+    component
+}
+
+/// CycloneDX 1.5 (<https://cyclonedx.org/docs/1.5/json/>): every tool is an application,
+/// with its dependencies and interpreter as nested components.
+pub fn cyclonedx(
+    tools: &[SbomTool],
+    timestamp: &str,
+) -> Value {
+    let mut components = Vec::with_capacity(tools.len());
+    let mut dependencies = Vec::with_capacity(tools.len());
+
+    for tool in tools {
+        let python_ref = bom_ref(tool, "python");
+        let mut nested: Vec<Value> = tool
+            .dependencies()
+            .map(|package| cyclonedx_component(tool, package, "library"))
+            .collect();
+        nested.push(json!({
+            "type": "platform",
+            "bom-ref": python_ref,
+            "name": "python",
+            "version": tool.python_version,
+            "properties": [{"name": "uvenv:python:executable", "value": tool.python_executable}],
+        }));
+
+        let mut component = tool.main_package().map_or_else(
+            || {
+                // e.g. a venv created with `uvenv create`, without a main package
+                json!({"type": "application", "bom-ref": bom_ref(tool, &tool.venv), "name": tool.venv})
+            },
+            |package| cyclonedx_component(tool, package, "application"),
+        );
+        component["components"] = Value::Array(nested);
+        components.push(component);
+
+        let mut depends_on: Vec<String> = tool
+            .dependencies()
+            .map(|package| bom_ref(tool, &package.name))
+            .collect();
+        depends_on.push(python_ref);
+        dependencies.push(json!({"ref": bom_ref(tool, &tool.venv), "dependsOn": depends_on}));
+    }
+
+    json!({
+        "bomFormat": "CycloneDX",
+        "specVersion": "1.5",
+        "version": 1,
+        "metadata": {
+            "timestamp": timestamp,
+            "tools": {
+                "components": [{"type": "application", "name": "uvenv", "version": uvenv_version()}],
+            },
+        },
+        "components": components,
+        "dependencies": dependencies,
+    })
+}
+
+/// SPDX identifiers may only contain letters, numbers, '.' and '-'.
+fn spdx_id(
+    tool: &SbomTool,
+    name: &str,
+) -> String {
+    let id: String = bom_ref(tool, name)
+        .chars()
+        .map(|chr| {
+            if chr.is_ascii_alphanumeric() || chr == '.' || chr == '-' {
+                chr
+            } else {
+                '-'
+            }
+        })
+        .collect();
+
+    format!("SPDXRef-{id}")
+}
+
+fn spdx_package(
+    tool: &SbomTool,
+    package: &SbomPackage,
+) -> Value {
+    let mut result = json!({
+        "SPDXID": spdx_id(tool, &package.name),
+        "name": package.name,
+        "versionInfo": package.version,
+        "downloadLocation": "NOASSERTION",
+        "filesAnalyzed": false,
+        "externalRefs": [{
+            "referenceCategory": "PACKAGE-MANAGER",
+            "referenceType": "purl",
+            "referenceLocator": package.purl(),
+        }],
+    });
+
+    if let Some(sha256) = &package.sha256 {
+        result["checksums"] = json!([{"algorithm": "SHA256", "checksumValue": sha256}]);
+    }
+
+    result
+}
+
+/// SPDX 2.3 (<https://spdx.github.io/spdx-spec/v2.3/>): the document describes every tool,
+/// which depends on the other packages in its venv and its interpreter.
+pub fn spdx(
+    tools: &[SbomTool],
+    timestamp: &str,
+) -> Value {
+    let mut packages = Vec::new();
+    let mut relationships = Vec::new();
+
+    for tool in tools {
+        let tool_id = spdx_id(tool, &tool.venv);
+        packages.push(tool.main_package().map_or_else(
+            || {
+                json!({
+                    "SPDXID": tool_id,
+                    "name": tool.venv,
+                    "downloadLocation": "NOASSERTION",
+                    "filesAnalyzed": false,
+                })
+            },
+            |package| spdx_package(tool, package),
+        ));
+        relationships.push(json!({
+            "spdxElementId": "SPDXRef-DOCUMENT",
+            "relationshipType": "DESCRIBES",
+            "relatedSpdxElement": tool_id,
+        }));
+
+        let python_id = spdx_id(tool, "python");
+        packages.push(json!({
+            "SPDXID": python_id,
+            "name": "python",
+            "versionInfo": tool.python_version,
+            "downloadLocation": "NOASSERTION",
+            "filesAnalyzed": false,
+            "comment": tool.python_executable,
+        }));
+
+        let mut related = vec![python_id];
+        for package in tool.dependencies() {
+            packages.push(spdx_package(tool, package));
+            related.push(spdx_id(tool, &package.name));
+        }
+
+        for related_id in related {
+            relationships.push(json!({
+                "spdxElementId": tool_id,
+                "relationshipType": "DEPENDS_ON",
+                "relatedSpdxElement": related_id,
+            }));
+        }
+    }
+
+    json!({
+        "spdxVersion": "SPDX-2.3",
+        "dataLicense": "CC0-1.0",
+        "SPDXID": "SPDXRef-DOCUMENT",
+        "name": "uvenv",
+        "documentNamespace": format!("https://spdx.org/spdxdocs/uvenv-{timestamp}"),
+        "creationInfo": {
+            "created": timestamp,
+            "creators": [format!("Tool: uvenv-{}", uvenv_version())],
+        },
+        "packages": packages,
+        "relationships": relationships,
+    })
+}
+
+pub async fn generate_sbom(
+    venv_names: &[String],
+    format: SbomFormat,
+) -> anyhow::Result<Value> {
+    let items = list_packages(&LoadMetadataConfig::none(), Some(venv_names), None).await?;
+
+    let mut tools = Vec::with_capacity(items.len());
+    for metadata in &items {
+        let tool = collect_tool(&metadata.name)
+            .with_context(|| format!("Could not read the packages of '{}'", metadata.name))?;
+        tools.push(tool);
+    }
+
+    let timestamp = Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true);
+    Ok(match format {
+        SbomFormat::CyclonedxJson => cyclonedx(&tools, &timestamp),
+        SbomFormat::SpdxJson => spdx(&tools, &timestamp),
+    })
+}
+
+impl Process for SbomOptions {
+    async fn process(self) -> anyhow::Result<i32> {
+        let sbom = generate_sbom(&self.venv_names, self.format)
+            .await
+            .with_context(|| "Something went wrong while generating the SBOM;")?;
+
+        println!("{}", serde_json::to_string_pretty(&sbom)?);
+        Ok(0)
+    }
+}
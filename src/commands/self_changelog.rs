@@ -1,4 +1,6 @@
 use crate::cli::{Process, SelfChangelogOptions};
+use crate::errors::UvenvError;
+use crate::offline::is_offline;
 use anyhow::{anyhow, Context};
 use owo_colors::OwoColorize;
 use regex::Regex;
@@ -6,6 +8,8 @@ use std::collections::BTreeMap;
 
 const CHANGELOG_URL: &str =
     "https://raw.githubusercontent.com/robinvandernoord/uvenv/uvenv/CHANGELOG.md";
+/// The changelog at build time, for offline use.
+const BUNDLED_CHANGELOG: &str = include_str!("../../CHANGELOG.md");
 
 type Changelogs = BTreeMap<String, BTreeMap<String, Vec<String>>>;
 
@@ -66,7 +70,9 @@ async fn _get_changelog() -> reqwest::Result<String> {
 }
 
 pub async fn get_changelog() -> anyhow::Result<String> {
-    _get_changelog().await.map_err(|err| anyhow!(err)) // reqwest to anyhow
+    _get_changelog()
+        .await
+        .map_err(|err| anyhow!(err).context(UvenvError::NetworkError(CHANGELOG_URL.to_owned())))
 }
 
 fn color(category: &str) -> String {
@@ -107,8 +113,23 @@ pub fn display_changelog(changelog: &Changelogs) {
     }
 }
 
+/// The latest changelog, or the bundled one when offline (or GitHub can't be reached).
+async fn load_changelog() -> String {
+    if is_offline() {
+        return BUNDLED_CHANGELOG.to_owned();
+    }
+
+    get_changelog().await.unwrap_or_else(|err| {
+        eprintln!(
+            "{} ({err}), showing the changelog of this version instead.",
+            "Could not load the latest changelog".yellow()
+        );
+        BUNDLED_CHANGELOG.to_owned()
+    })
+}
+
 pub async fn changelog() -> anyhow::Result<i32> {
-    let md = get_changelog().await?;
+    let md = load_changelog().await;
     let parsed = parse_changelog(&md);
 
     display_changelog(&parsed);
@@ -6,6 +6,7 @@ use crate::animate::{show_loading_indicator, AnimationSettings};
 use crate::cli::{Process, SelfUpdateOptions};
 use crate::cmd::{find_sibling, run};
 use crate::helpers::PathAsStr;
+use crate::offline::is_offline;
 use crate::pip::pip_freeze;
 use crate::uv::{system_environment, uv_freeze, PythonSpecifier};
 use owo_colors::OwoColorize;
@@ -235,6 +236,10 @@ pub async fn self_update(
 
 impl Process for SelfUpdateOptions {
     async fn process(self) -> anyhow::Result<i32> {
+        if is_offline() {
+            bail!("uvenv can't be updated in offline mode.");
+        }
+
         self_update(!self.without_uv, !self.without_patchelf)
             .await
             .with_context(|| "Something went wrong trying to update 'uvenv';")
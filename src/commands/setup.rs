@@ -1,15 +1,16 @@
+use anyhow::Context;
 use owo_colors::OwoColorize;
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 
 use crate::cli::{Process, SetupOptions};
-use crate::cmd::run_if_bash_else_warn;
 use crate::commands::activate::install_activate;
 use crate::commands::completions::completions;
-use crate::commands::ensurepath::ensure_path;
+use crate::commands::ensurepath::{ensure_path, read_from_rc, remove_from_rc};
 use crate::commands::self_link::self_link;
-use crate::helpers::fmt_error;
-use crate::metadata::{get_work_dir, load_generic_msgpack, store_generic_msgpack};
+use crate::helpers::{fmt_error, PathAsStr};
+use crate::metadata::{ensure_bin_dir, get_work_dir, load_generic_msgpack, store_generic_msgpack};
+use crate::shells::SupportedShell;
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize, Deserialize)]
 pub struct SetupMetadata {
@@ -61,7 +62,22 @@ pub async fn store_setup_metadata(metadata: &SetupMetadata) -> anyhow::Result<()
     store_generic_msgpack(&filename, metadata).await
 }
 
-pub async fn setup_for_bash(
+/// Make the stored features match what the rc file actually contains
+/// (e.g. when the uvenv block was edited or removed by hand).
+pub async fn reconcile_setup_metadata(shell: SupportedShell) -> anyhow::Result<SetupMetadata> {
+    let lines = read_from_rc(shell).await?;
+    let bin_path = ensure_bin_dir().await;
+
+    let mut metadata = load_setup_metadata().await;
+    metadata.feature_ensurepath = lines.contains(&shell.path_snippet(bin_path.as_str()));
+    metadata.feature_completions = lines.contains(&shell.eval_line("completions"));
+    metadata.feature_activate = lines.contains(&shell.eval_line("activate _"));
+
+    Ok(metadata)
+}
+
+pub async fn setup_for_shell(
+    shell: SupportedShell,
     do_ensurepath: bool,
     do_completions: bool,
     do_activate: bool,
@@ -69,10 +85,17 @@ pub async fn setup_for_bash(
 ) -> anyhow::Result<i32> {
     let mut any_warnings = false;
 
-    let mut metadata = load_setup_metadata().await;
+    let mut metadata = match reconcile_setup_metadata(shell).await {
+        Ok(metadata) => metadata,
+        Err(msg) => {
+            any_warnings = true;
+            eprintln!("{}", fmt_error(&msg));
+            load_setup_metadata().await
+        },
+    };
 
     if do_ensurepath && (!metadata.feature_ensurepath || force) {
-        if let Err(msg) = ensure_path(force).await {
+        if let Err(msg) = ensure_path(shell, force).await {
             any_warnings = true;
             eprintln!("{}", fmt_error(&msg));
         }
@@ -80,7 +103,7 @@ pub async fn setup_for_bash(
     }
 
     if do_completions && (!metadata.feature_completions || force) {
-        if let Err(msg) = completions(true).await {
+        if let Err(msg) = completions(shell, true).await {
             any_warnings = true;
             eprintln!("{}", fmt_error(&msg));
         }
@@ -88,7 +111,7 @@ pub async fn setup_for_bash(
     }
 
     if do_activate && (!metadata.feature_activate || force) {
-        if let Err(msg) = install_activate().await {
+        if let Err(msg) = install_activate(shell).await {
             any_warnings = true;
             eprintln!("{}", fmt_error(&msg));
         }
@@ -103,36 +126,46 @@ pub async fn setup_for_bash(
     // ignore result/output:
     let _ = self_link(false, true).await;
 
-    println!("Setup finished, you may want to run `{}` now in order to apply these changes to your shell.", "exec bash".green());
+    println!(
+        "Setup finished, you may want to run `{}` now in order to apply these changes to your shell.",
+        format!("exec {}", shell.name()).green()
+    );
     // bool to int
     Ok(i32::from(any_warnings))
 }
 
+pub async fn undo_setup(shell: SupportedShell) -> anyhow::Result<i32> {
+    remove_from_rc(shell).await?;
+    store_setup_metadata(&SetupMetadata::new()).await?;
+
+    println!(
+        "Removed uvenv from {}, you may want to run `{}` now in order to apply these changes to your shell.",
+        shell.rc_file().as_str(),
+        format!("exec {}", shell.name()).green()
+    );
+    Ok(0)
+}
+
 impl Process for SetupOptions {
     async fn process(self) -> anyhow::Result<i32> {
-        let result = run_if_bash_else_warn(move |_| {
-            // some logic here
-            let result = setup_for_bash(
-                !self.skip_ensurepath,
-                !self.skip_completions,
-                !self.skip_activate,
-                self.force,
-            );
-
-            // async is not possible in this block,
-            // creating a run_if_bash_else_warn_async is non-trivial
-            Some(result) // so just return a promise
-        });
-
-        match result {
-            Some(promise) => {
-                // finally, we can await
-                promise.await
-            },
-            None => {
-                // unsupported shell ->
-                Ok(126)
-            },
+        let Some(shell) = SupportedShell::detect_or_warn() else {
+            // unsupported shell ->
+            return Ok(126);
+        };
+
+        if self.undo {
+            return undo_setup(shell)
+                .await
+                .with_context(|| "Something went wrong trying to undo the setup;");
         }
+
+        setup_for_shell(
+            shell,
+            !self.skip_ensurepath,
+            !self.skip_completions,
+            !self.skip_activate,
+            self.force,
+        )
+        .await
     }
 }
@@ -0,0 +1,130 @@
+use anyhow::Context;
+use core::fmt::Write;
+use owo_colors::OwoColorize;
+use std::env;
+use std::ffi::OsStr;
+use std::path::Path;
+use tempfile::TempDir;
+use tokio::process::Command;
+
+use crate::cli::{Process, ShellOptions};
+use crate::cmd::exit_code;
+use crate::helpers::PathAsStr;
+use crate::venv::setup_environ_from_requirement;
+
+/// Quote a string for POSIX shells (bash, zsh, sh).
+fn posix_quote(text: &str) -> String {
+    format!("'{}'", text.replace('\'', r"'\''"))
+}
+
+/// Quote a string for fish.
+fn fish_quote(text: &str) -> String {
+    format!("'{}'", text.replace('\\', r"\\").replace('\'', r"\'"))
+}
+
+/// Prepare a `Command` that starts `shell` interactively, sourcing the user's own config first
+/// and the venv's activate script (which also prefixes the prompt) after that.
+fn build_shell_command(
+    shell: &str,
+    venv: &Path,
+    tmp: &TempDir,
+) -> anyhow::Result<Command> {
+    let mut cmd = Command::new(shell);
+    let activate = venv.join("bin").join("activate");
+    let activate_posix = posix_quote(activate.as_str());
+
+    let shell_name = Path::new(shell)
+        .file_name()
+        .and_then(OsStr::to_str)
+        .unwrap_or_default();
+
+    match shell_name {
+        "bash" => {
+            let rcfile = tmp.path().join("bashrc");
+            std::fs::write(
+                &rcfile,
+                format!("[ -f ~/.bashrc ] && . ~/.bashrc\n. {activate_posix}\n"),
+            )?;
+            cmd.arg("--rcfile").arg(rcfile).arg("-i");
+        },
+        "zsh" => {
+            // zsh reads its config from $ZDOTDIR, so point that to a temporary directory
+            // which restores the original ZDOTDIR before loading the user's files.
+            let zdotdir = posix_quote(
+                &env::var("ZDOTDIR").unwrap_or_else(|_| env::var("HOME").unwrap_or_default()),
+            );
+            std::fs::write(
+                tmp.path().join(".zshenv"),
+                format!("[ -f {zdotdir}/.zshenv ] && . {zdotdir}/.zshenv\n"),
+            )?;
+            std::fs::write(
+                tmp.path().join(".zshrc"),
+                format!(
+                    "ZDOTDIR={zdotdir}\n[ -f \"$ZDOTDIR/.zshrc\" ] && . \"$ZDOTDIR/.zshrc\"\n. {activate_posix}\n"
+                ),
+            )?;
+            cmd.env("ZDOTDIR", tmp.path()).arg("-i");
+        },
+        "fish" => {
+            let activate_fish = fish_quote(venv.join("bin").join("activate.fish").as_str());
+            // --init-command runs after the regular config files:
+            cmd.arg("--init-command")
+                .arg(format!("source {activate_fish}"));
+        },
+        _ => {
+            // POSIX sh: interactive shells source the file in $ENV
+            let envfile = tmp.path().join("shrc");
+            let mut content = String::new();
+            if let Ok(original) = env::var("ENV") {
+                writeln!(content, "[ -f {0} ] && . {0}", posix_quote(&original))?;
+            }
+            writeln!(content, ". {activate_posix}")?;
+            std::fs::write(&envfile, content)?;
+            cmd.env("ENV", envfile).arg("-i");
+        },
+    }
+
+    Ok(cmd)
+}
+
+pub async fn spawn_shell(venv_name: &str) -> anyhow::Result<i32> {
+    let (_, environ) = setup_environ_from_requirement(venv_name).await?;
+
+    let shell = env::var("SHELL").unwrap_or_else(|_| String::from("/bin/sh"));
+
+    // holds the generated rc files until the shell exits:
+    let tmp = tempfile::tempdir()?;
+    let mut cmd = build_shell_command(&shell, environ.root(), &tmp)?;
+
+    eprintln!(
+        "🐚 Starting {} with '{}' activated. Use `{}` to leave.",
+        shell.blue(),
+        venv_name.green(),
+        "exit".green()
+    );
+
+    let mut child = cmd
+        .spawn()
+        .with_context(|| format!("Could not start shell '{shell}'"))?;
+
+    // Ctrl-C is meant for the subshell (which handles it itself), so it shouldn't kill uvenv:
+    let status = loop {
+        tokio::select! {
+            status = child.wait() => break status?,
+            _ = tokio::signal::ctrl_c() => {},
+        }
+    };
+
+    Ok(exit_code(status))
+}
+
+impl Process for ShellOptions {
+    async fn process(self) -> anyhow::Result<i32> {
+        spawn_shell(&self.venv_name).await.with_context(|| {
+            format!(
+                "Something went wrong trying to start a shell in '{}';",
+                &self.venv_name
+            )
+        })
+    }
+}
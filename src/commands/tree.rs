@@ -0,0 +1,316 @@
+use anyhow::Context;
+use core::fmt::Write;
+use core::str::FromStr;
+use itertools::Itertools;
+use owo_colors::OwoColorize;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashSet};
+use uv_pep508::{ExtraName, MarkerEnvironment, PackageName, Requirement, VerbatimUrl};
+
+use crate::cli::{Process, TreeOptions};
+use crate::commands::list::list_packages;
+use crate::errors::GENERIC_EXIT_CODE;
+use crate::metadata::{venv_path, LoadMetadataConfig, Metadata};
+use crate::output::{is_json, strip_ansi};
+use crate::uv::{environment_from_root, uv_installed_packages, InstalledPackage};
+use uv_python::PythonEnvironment;
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize)]
+pub struct DependencyNode {
+    pub name: String,
+    /// empty if the package is required but not installed
+    pub version: String,
+    pub injected: bool,
+    /// already shown elsewhere in the tree, so its dependencies are not repeated
+    pub repeated: bool,
+    pub dependencies: Vec<DependencyNode>,
+    /// why the tree of this venv could not be read (only for the root)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl DependencyNode {
+    fn label(&self) -> String {
+        let mut label = if self.version.is_empty() {
+            format!("{} {}", self.name, "(not installed)".red())
+        } else {
+            format!("{} {}", self.name, self.version.cyan())
+        };
+
+        if self.injected {
+            label.push_str(" 💉");
+        }
+        if self.repeated {
+            label.push_str(" (*)");
+        }
+
+        label
+    }
+
+    /// e.g.
+    ///     httpie 3.2.2
+    ///     ├── charset-normalizer 3.3.2
+    ///     └── rich 13.7.1 💉
+    pub fn format_human(&self) -> anyhow::Result<String> {
+        let mut result = String::new();
+        writeln!(result, "{}", self.label().bold())?;
+        self.format_children("", &mut result)?;
+
+        Ok(result)
+    }
+
+    fn format_children(
+        &self,
+        prefix: &str,
+        result: &mut String,
+    ) -> anyhow::Result<()> {
+        let amount = self.dependencies.len();
+
+        for (idx, child) in self.dependencies.iter().enumerate() {
+            let is_last = idx + 1 == amount;
+            let (branch, indent) = if is_last {
+                ("└── ", "    ")
+            } else {
+                ("├── ", "│   ")
+            };
+
+            writeln!(result, "{prefix}{branch}{}", child.label())?;
+            child.format_children(&format!("{prefix}{indent}"), result)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Walks the requirements of installed packages, like `cargo tree`:
+/// every package is expanded only once, later occurrences are marked as repeated.
+struct TreeBuilder<'env> {
+    packages: &'env BTreeMap<PackageName, InstalledPackage>,
+    markers: &'env MarkerEnvironment,
+    injected: HashSet<PackageName>,
+    seen: HashSet<PackageName>,
+}
+
+impl TreeBuilder<'_> {
+    fn node(
+        &mut self,
+        name: &PackageName,
+        extras: &[ExtraName],
+    ) -> DependencyNode {
+        let mut node = DependencyNode {
+            name: name.to_string(),
+            injected: self.injected.contains(name),
+            ..Default::default()
+        };
+
+        let Some(package) = self.packages.get(name) else {
+            return node;
+        };
+        node.version.clone_from(&package.version);
+
+        if !self.seen.insert(name.clone()) {
+            node.repeated = true;
+            return node;
+        }
+
+        // the same package can be required more than once (e.g. with different markers or extras):
+        let mut requirements: BTreeMap<PackageName, Vec<ExtraName>> = BTreeMap::new();
+        for requirement in package
+            .requires_dist
+            .iter()
+            .filter(|requirement| requirement.evaluate_markers(self.markers, extras))
+        {
+            let merged = requirements.entry(requirement.name.clone()).or_default();
+            merged.extend(requirement.extras.iter().cloned());
+            merged.sort();
+            merged.dedup();
+        }
+
+        node.dependencies = requirements
+            .iter()
+            .map(|(dependency, dependency_extras)| self.node(dependency, dependency_extras))
+            .collect();
+
+        node
+    }
+}
+
+/// Injected packages are stored as they were passed to `uvenv inject` (e.g. 'rich>=13').
+pub fn injected_names(metadata: &Metadata) -> Vec<PackageName> {
+    metadata
+        .vec_injected()
+        .into_iter()
+        .filter_map(|spec| {
+            Requirement::<VerbatimUrl>::from_str(spec)
+                .map(|requirement| requirement.name)
+                .ok()
+        })
+        .sorted()
+        .collect()
+}
+
+/// The packages that are not a (transitive) dependency of any of the other packages, sorted.
+/// Within a dependency cycle (e.g. a -> b -> a) the first one by name counts as the root.
+pub fn root_packages<F>(
+    packages: &[PackageName],
+    mut dependencies_of: F,
+) -> Vec<PackageName>
+where
+    F: FnMut(&PackageName) -> HashSet<PackageName>,
+{
+    let dependencies: BTreeMap<&PackageName, HashSet<PackageName>> = packages
+        .iter()
+        .map(|package| {
+            let mut transitive = dependencies_of(package);
+            transitive.remove(package);
+            (package, transitive)
+        })
+        .collect();
+
+    let required: HashSet<&PackageName> = dependencies.values().flatten().collect();
+    let mut roots: Vec<PackageName> = packages
+        .iter()
+        .filter(|package| !required.contains(package))
+        .cloned()
+        .collect();
+
+    // whatever is left is only required from within a cycle:
+    let mut covered: HashSet<&PackageName> = roots
+        .iter()
+        .filter_map(|root| dependencies.get(root))
+        .flatten()
+        .collect();
+    for package in packages.iter().sorted() {
+        if roots.contains(package) || covered.contains(package) {
+            continue;
+        }
+        roots.push(package.clone());
+        covered.extend(dependencies.get(package).into_iter().flatten());
+    }
+
+    roots.sort();
+    roots
+}
+
+/// Installed packages that are not (transitive) dependencies of `name` or of each other,
+/// e.g. to recover the injected packages of a venv without metadata.
+pub fn unrequired_packages(
+    environ: &PythonEnvironment,
+    name: &PackageName,
+) -> anyhow::Result<Vec<PackageName>> {
+    let packages = uv_installed_packages(environ)?;
+    let markers = environ.interpreter().markers();
+
+    let dependencies_of = |package: &PackageName| {
+        let mut builder = TreeBuilder {
+            packages: &packages,
+            markers,
+            injected: HashSet::new(),
+            seen: HashSet::new(),
+        };
+        builder.node(package, &[]);
+        builder.seen
+    };
+
+    let required = dependencies_of(name);
+    let unrequired: Vec<PackageName> = packages
+        .keys()
+        .filter(|package| !required.contains(*package))
+        .cloned()
+        .collect();
+
+    Ok(root_packages(&unrequired, dependencies_of))
+}
+
+/// The dependency tree of a venv: the main package with its (transitive) dependencies,
+/// followed by the injected packages.
+pub fn dependency_tree(metadata: &Metadata) -> anyhow::Result<DependencyNode> {
+    let environ = environment_from_root(&venv_path(&metadata.name))?;
+    let packages = uv_installed_packages(&environ)?;
+
+    let injected = injected_names(metadata);
+    let mut builder = TreeBuilder {
+        packages: &packages,
+        markers: environ.interpreter().markers(),
+        injected: injected.iter().cloned().collect(),
+        seen: HashSet::new(),
+    };
+
+    let name = PackageName::from_str(&metadata.name)?;
+    let extras: Vec<ExtraName> = metadata
+        .extras
+        .iter()
+        .filter_map(|extra| ExtraName::from_str(extra).ok())
+        .collect();
+
+    let mut root = builder.node(&name, &extras);
+    for injected_name in &injected {
+        let injected_node = builder.node(injected_name, &[]);
+        root.dependencies.push(injected_node);
+    }
+
+    Ok(root)
+}
+
+pub async fn print_trees(
+    venv_names: &[String],
+    json: bool,
+) -> anyhow::Result<i32> {
+    let items = list_packages(&LoadMetadataConfig::none(), Some(venv_names), None).await?;
+
+    print_dependency_trees(&items, json)
+}
+
+/// Print the trees of already loaded (and possibly filtered or sorted) venvs.
+/// A venv that can't be read is reported (as `error` in JSON) without stopping the others.
+pub fn print_dependency_trees(
+    items: &[Metadata],
+    json: bool,
+) -> anyhow::Result<i32> {
+    let trees: Vec<DependencyNode> = items
+        .iter()
+        .map(|metadata| {
+            dependency_tree(metadata).unwrap_or_else(|err| DependencyNode {
+                name: metadata.name.clone(),
+                error: Some(strip_ansi(&format!("{err:#}"))),
+                ..Default::default()
+            })
+        })
+        .collect();
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&trees)?);
+    } else {
+        let mut separate = false;
+        for tree in &trees {
+            if let Some(error) = &tree.error {
+                eprintln!(
+                    "⚠️ Could not read the dependencies of '{}': {}",
+                    tree.name.green(),
+                    error.yellow()
+                );
+                continue;
+            }
+
+            if separate {
+                println!();
+            }
+            separate = true;
+            print!("{}", tree.format_human()?);
+        }
+    }
+
+    if trees.iter().any(|tree| tree.error.is_some()) {
+        Ok(GENERIC_EXIT_CODE)
+    } else {
+        Ok(0)
+    }
+}
+
+impl Process for TreeOptions {
+    async fn process(self) -> anyhow::Result<i32> {
+        print_trees(&self.venv_names, self.json || is_json())
+            .await
+            .with_context(|| "Something went wrong trying to show the dependency tree;")
+    }
+}
@@ -1,6 +1,8 @@
 use crate::animate::{show_loading_indicator, AnimationSettings};
 use crate::cli::{Process, UnInjectOptions};
+use crate::commands::install::sync_symlinks;
 use crate::metadata::{LoadMetadataConfig, Metadata};
+use crate::output::{Action, ActionResult};
 use crate::venv::setup_environ_from_requirement;
 use anyhow::anyhow;
 use itertools::Itertools;
@@ -11,9 +13,9 @@ use crate::uv::{uv, Helpers};
 pub async fn eject_package(
     from: &str,
     to_eject_specs: &[String], // .contains is used, so String is required.
-) -> anyhow::Result<String> {
+) -> anyhow::Result<ActionResult> {
     let (requirement, environ) = setup_environ_from_requirement(from).await?;
-    let mut metadata = Metadata::for_requirement(&requirement, &LoadMetadataConfig::none()).await;
+    let mut metadata = Metadata::for_requirement(&requirement, &LoadMetadataConfig::none()).await?;
 
     let mut args = vec!["pip", "uninstall"];
 
@@ -38,19 +40,26 @@ pub async fn eject_package(
         .collect();
 
     metadata.save(&environ.to_path_buf()).await?;
+    let changes = sync_symlinks(&mut metadata, &environ, &requirement).await?;
 
-    Ok(format!(
-        "⏏️  Ejected [{}] from {}.",
-        &to_eject_str,
-        &metadata.name.green(),
-    ))
+    let mut result = ActionResult::new(Action::Uninject, &metadata.name)
+        .with_message(format!(
+            "⏏️  Ejected [{}] from {}.",
+            &to_eject_str,
+            &metadata.name.green(),
+        ))
+        .with_changes(&changes);
+    result.old_version.clone_from(&metadata.installed_version);
+    result.new_version.clone_from(&metadata.installed_version);
+
+    Ok(result)
 }
 
 impl Process for UnInjectOptions {
     async fn process(self) -> anyhow::Result<i32> {
         match eject_package(&self.outof, &self.package_specs).await {
-            Ok(msg) => {
-                println!("{msg}");
+            Ok(result) => {
+                result.print()?;
                 Ok(0)
             },
             Err(msg) => Err(anyhow!(msg)),
@@ -4,14 +4,16 @@ use anyhow::{bail, Context};
 use owo_colors::OwoColorize;
 
 use crate::cli::{Process, UninstallOptions};
+use crate::errors::UvenvError;
 use crate::metadata::{venv_path, LoadMetadataConfig, Metadata};
+use crate::output::{Action, ActionResult};
 use crate::symlinks::{find_symlinks, remove_symlink, remove_symlinks};
 use crate::venv::{activate_venv, remove_venv};
 
 pub async fn uninstall_package(
     package_name: &str,
     force: bool,
-) -> anyhow::Result<String> {
+) -> anyhow::Result<ActionResult> {
     let (requirement, _) = parse_requirement(package_name).await?;
     let requirement_name = requirement.name.to_string();
 
@@ -27,14 +29,20 @@ pub async fn uninstall_package(
                 &requirement_name.green()
             )
         } else {
-            bail!("No virtualenv for '{}', stopping.\nUse '{}' to remove an executable with that name anyway.",
-                                        &requirement_name.green(), "--force".green())
+            eprintln!(
+                "💡 Use '{}' to remove an executable with that name anyway.",
+                "--force".green()
+            );
+            return Err(UvenvError::VenvNotFound(requirement_name).into());
         };
     }
 
     let venv = activate_venv(&venv_dir).await?;
 
-    let metadata = Metadata::for_requirement(&requirement, &LoadMetadataConfig::none()).await;
+    // a corrupt `.metadata` shouldn't prevent removing the venv:
+    let metadata = Metadata::for_requirement(&requirement, &LoadMetadataConfig::none())
+        .await
+        .unwrap_or_else(|_| Metadata::find(&requirement));
 
     // symlinks = find_symlinks(package_name, venv_path) or [package_name]
     let symlinks = find_symlinks(&requirement, &metadata.installed_version, &venv).await;
@@ -49,16 +57,19 @@ pub async fn uninstall_package(
         format!(" ({})", metadata.installed_version.cyan())
     };
 
-    let msg = format!("🗑️  {package_name}{version_msg} removed!");
+    let mut result = ActionResult::new(Action::Uninstall, &requirement_name)
+        .with_message(format!("🗑️  {package_name}{version_msg} removed!"));
+    result.old_version = metadata.installed_version;
+    result.scripts_unlinked = symlinks;
 
-    Ok(msg)
+    Ok(result)
 }
 
 impl Process for UninstallOptions {
     async fn process(self) -> anyhow::Result<i32> {
         match uninstall_package(&self.package_name, self.force).await {
-            Ok(msg) => {
-                println!("{msg}");
+            Ok(result) => {
+                result.print()?;
                 Ok(0)
             },
             Err(msg) => Err(msg).with_context(|| {
@@ -2,6 +2,7 @@ use crate::cli::{Process, UninstallAllOptions};
 use crate::commands::list::list_packages;
 use crate::commands::uninstall::uninstall_package;
 use crate::metadata::LoadMetadataConfig;
+use crate::output::{is_json, print_results_json, Action, ActionResult};
 use anyhow::{anyhow, Context};
 
 pub async fn uninstall_all(
@@ -9,21 +10,30 @@ pub async fn uninstall_all(
     venv_names: &[String],
 ) -> anyhow::Result<()> {
     let mut all_ok = true;
+    let mut results = Vec::new();
     let mut err_result = Err(anyhow!("-> Failed uninstall-all."));
 
     for meta in list_packages(&LoadMetadataConfig::none(), Some(venv_names), None).await? {
         match uninstall_package(&meta.name, force).await {
-            Ok(msg) => {
-                println!("{msg}");
+            Ok(result) => {
+                if !is_json() {
+                    println!("{result}");
+                }
+                results.push(result);
             },
             Err(msg) => {
                 // eprintln!("{}", msg.red());
+                results.push(ActionResult::failed(Action::Uninstall, &meta.name, &msg));
                 err_result = err_result.with_context(|| msg);
                 all_ok = false;
             },
         }
     }
 
+    if is_json() {
+        print_results_json(&results)?;
+    }
+
     if all_ok {
         Ok(())
     } else {
@@ -35,6 +45,8 @@ impl Process for UninstallAllOptions {
     async fn process(self) -> anyhow::Result<i32> {
         match uninstall_all(self.force, &self.venv_names).await {
             Ok(()) => Ok(0),
+            // the errors are already part of the json output:
+            Err(_) if is_json() => Ok(1),
             Err(msg) => Err(msg),
         }
     }
@@ -5,8 +5,10 @@ use owo_colors::OwoColorize;
 use uv_pep508::Requirement;
 use uv_python::PythonEnvironment;
 
+use crate::commands::install::sync_symlinks;
 use crate::helpers::StringExt;
 use crate::metadata::LoadMetadataConfig;
+use crate::output::{Action, ActionResult};
 use crate::venv::setup_environ_from_requirement;
 use crate::{
     animate::{show_loading_indicator, AnimationSettings},
@@ -87,7 +89,7 @@ pub async fn _upgrade_package(
     force: bool,
     no_cache: bool,
     skip_injected: bool,
-) -> anyhow::Result<String> {
+) -> anyhow::Result<ActionResult> {
     let old_version = metadata.installed_version.clone();
 
     let mut args = vec!["pip", "install", "--upgrade"];
@@ -132,8 +134,15 @@ pub async fn _upgrade_package(
     .await?;
 
     let new_version = update_metadata(metadata, requirement, environ, version).await?;
+    let changes = sync_symlinks(metadata, environ, requirement).await?;
+
+    let mut result = ActionResult::new(Action::Upgrade, &metadata.name)
+        .with_message(build_msg(&old_version, &new_version, metadata))
+        .with_changes(&changes);
+    result.old_version = old_version;
+    result.new_version = new_version;
 
-    Ok(build_msg(&old_version, &new_version, metadata))
+    Ok(result)
 }
 
 pub async fn upgrade_package(
@@ -141,7 +150,7 @@ pub async fn upgrade_package(
     force: bool,
     no_cache: bool,
     skip_injected: bool,
-) -> anyhow::Result<String> {
+) -> anyhow::Result<ActionResult> {
     // No virtualenv for '{package_name}', stopping. Use 'uvenv install' instead.
     let (requirement, environ) = setup_environ_from_requirement(install_spec).await?;
 
@@ -151,7 +160,7 @@ pub async fn upgrade_package(
         ..Default::default()
     };
 
-    let mut metadata = Metadata::for_requirement(&requirement, &config).await;
+    let mut metadata = Metadata::for_requirement(&requirement, &config).await?;
 
     _upgrade_package(
         &requirement,
@@ -174,8 +183,8 @@ impl Process for UpgradeOptions {
         )
         .await
         {
-            Ok(msg) => {
-                println!("{msg}");
+            Ok(result) => {
+                result.print()?;
                 Ok(0)
             },
             Err(msg) => Err(msg).with_context(|| {
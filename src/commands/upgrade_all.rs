@@ -2,6 +2,7 @@ use crate::cli::{Process, UpgradeAllOptions};
 use crate::commands::list::list_packages;
 use crate::commands::upgrade::upgrade_package;
 use crate::metadata::LoadMetadataConfig;
+use crate::output::{is_json, print_results_json, Action, ActionResult};
 use anyhow::{anyhow, Context};
 
 pub async fn upgrade_all(
@@ -11,21 +12,30 @@ pub async fn upgrade_all(
     venv_names: &[String],
 ) -> anyhow::Result<()> {
     let mut all_ok = true;
+    let mut results = Vec::new();
     let mut err_result = Err(anyhow!("-> Failed upgrade-all."));
 
     for meta in list_packages(&LoadMetadataConfig::none(), Some(venv_names), None).await? {
         match upgrade_package(&meta.name, force, no_cache, skip_injected).await {
-            Ok(msg) => {
-                println!("{msg}");
+            Ok(result) => {
+                if !is_json() {
+                    println!("{result}");
+                }
+                results.push(result);
             },
             Err(msg) => {
                 // eprintln!("{}", msg.red());
+                results.push(ActionResult::failed(Action::Upgrade, &meta.name, &msg));
                 err_result = err_result.with_context(|| msg);
                 all_ok = false;
             },
         }
     }
 
+    if is_json() {
+        print_results_json(&results)?;
+    }
+
     if all_ok {
         Ok(())
     } else {
@@ -44,6 +54,8 @@ impl Process for UpgradeAllOptions {
         .await
         {
             Ok(()) => Ok(0),
+            // the errors are already part of the json output:
+            Err(_) if is_json() => Ok(1),
             Err(msg) => Err(msg),
         }
     }
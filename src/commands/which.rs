@@ -0,0 +1,168 @@
+use anyhow::Context;
+use owo_colors::OwoColorize;
+use serde::Serialize;
+use std::env;
+use std::os::unix::fs::PermissionsExt;
+use std::path::{Path, PathBuf};
+use uv_distribution_types::Name;
+use uv_installer::SitePackages;
+
+use crate::cli::{Process, WhichOptions};
+use crate::commands::list::list_packages;
+use crate::helpers::PathAsStr;
+use crate::metadata::{get_bin_dir, venv_path, LoadMetadataConfig};
+use crate::output::is_json;
+use crate::symlinks::{console_scripts, is_symlink, points_to};
+use crate::uv::environment_from_root;
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize)]
+pub struct Provider {
+    pub venv: String,
+    /// the distribution whose entry points declare the command
+    /// (empty if it's only known from uvenv's metadata, e.g. a script without entry point)
+    pub distribution: String,
+    /// whether the command in uvenv's bin dir links to this venv
+    pub linked: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize)]
+pub struct WhichResult {
+    pub command: String,
+    pub providers: Vec<Provider>,
+    /// e.g. ~/.local/bin/httpie
+    pub symlink: Option<String>,
+    /// e.g. ~/.local/uvenv/venvs/httpie/bin/httpie
+    pub target: Option<String>,
+    /// what the shell would actually run, if that's not uvenv's symlink
+    pub shadowed_by: Option<String>,
+}
+
+/// The distribution in a venv that declares `command` as a console script.
+async fn declaring_distribution(
+    venv_root: &Path,
+    command: &str,
+) -> anyhow::Result<Option<String>> {
+    let environ = environment_from_root(venv_root)?;
+    let site_packages = SitePackages::from_environment(&environ)?;
+
+    for dist in site_packages.iter() {
+        let entry_points = dist.path().join("entry_points.txt");
+        let scripts = console_scripts(entry_points.as_str())
+            .await
+            .unwrap_or_default();
+
+        if scripts.iter().any(|script| script == command) {
+            return Ok(Some(dist.name().to_string()));
+        }
+    }
+
+    Ok(None)
+}
+
+fn is_executable(path: &Path) -> bool {
+    path.metadata()
+        .is_ok_and(|meta| meta.is_file() && (meta.permissions().mode() & 0o111) != 0)
+}
+
+/// First executable called `command` on $PATH, like the shell would find it.
+fn find_on_path(command: &str) -> Option<PathBuf> {
+    let path = env::var_os("PATH")?;
+
+    env::split_paths(&path)
+        .map(|dir| dir.join(command))
+        .find(|candidate| is_executable(candidate))
+}
+
+pub async fn which(command: &str) -> anyhow::Result<WhichResult> {
+    let symlink_path = get_bin_dir().join(command);
+    let is_linked = is_symlink(&symlink_path);
+
+    let items = list_packages(&LoadMetadataConfig::none(), None, None).await?;
+
+    let mut providers = Vec::new();
+    for metadata in &items {
+        let root = venv_path(&metadata.name);
+        let distribution = declaring_distribution(&root, command)
+            .await
+            .with_context(|| format!("Could not read the packages of '{}'", metadata.name))?;
+
+        if distribution.is_none() && !metadata.scripts.contains_key(command) {
+            continue;
+        }
+
+        providers.push(Provider {
+            venv: metadata.name.clone(),
+            distribution: distribution.unwrap_or_default(),
+            linked: is_linked && points_to(&symlink_path, &root),
+        });
+    }
+
+    let target = is_linked
+        .then(|| std::fs::read_link(&symlink_path).ok())
+        .flatten();
+
+    // something else on $PATH only matters if uvenv links the command.
+    // Compare canonical paths, since $PATH could contain the bin dir via another symlink:
+    let shadowed_by = find_on_path(command)
+        .filter(|found| is_linked && found.canonicalize().ok() != symlink_path.canonicalize().ok());
+
+    Ok(WhichResult {
+        command: command.to_owned(),
+        providers,
+        symlink: is_linked.then(|| symlink_path.as_str().to_owned()),
+        target: target.map(|path| path.as_str().to_owned()),
+        shadowed_by: shadowed_by.map(|path| path.as_str().to_owned()),
+    })
+}
+
+fn print_human(result: &WhichResult) {
+    if result.providers.is_empty() {
+        println!(
+            "{} is not provided by any uvenv-managed venv.",
+            result.command.yellow()
+        );
+        return;
+    }
+
+    for provider in &result.providers {
+        let distribution = if provider.distribution.is_empty() {
+            String::new()
+        } else {
+            format!(" (from {})", provider.distribution.cyan())
+        };
+        let linked = if provider.linked { " 🔗" } else { "" };
+
+        println!(
+            "{} is provided by {}{distribution}{linked}",
+            result.command.green(),
+            provider.venv.bold()
+        );
+    }
+
+    if let (Some(symlink), Some(target)) = (&result.symlink, &result.target) {
+        println!("{} -> {}", symlink.blue(), target.blue());
+    }
+
+    if let Some(shadowed_by) = &result.shadowed_by {
+        println!(
+            "⚠️ Shadowed by {}, which comes earlier on your $PATH!",
+            shadowed_by.red()
+        );
+    }
+}
+
+impl Process for WhichOptions {
+    async fn process(self) -> anyhow::Result<i32> {
+        let result = which(&self.command)
+            .await
+            .with_context(|| format!("Something went wrong trying to find '{}';", self.command))?;
+
+        if self.json || is_json() {
+            println!("{}", serde_json::to_string_pretty(&result)?);
+        } else {
+            print_human(&result);
+        }
+
+        Ok(i32::from(result.providers.is_empty()))
+    }
+}
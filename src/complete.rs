@@ -0,0 +1,66 @@
+//! Dynamic completions (via `COMPLETE=<shell> uvenv`), so suggestions always reflect the installed venvs.
+//! Note: clap calls these candidate functions synchronously, so they can't use the async helpers.
+use clap_complete::engine::CompletionCandidate;
+use itertools::Itertools;
+use std::env;
+
+use crate::metadata::{get_venv_dir, strip_header, venv_path, Metadata};
+
+/// Names of all uvenv-managed venvs.
+pub fn venv_names() -> Vec<CompletionCandidate> {
+    let Ok(venvs) = std::fs::read_dir(get_venv_dir()) else {
+        return Vec::new();
+    };
+
+    venvs
+        .flatten()
+        .filter(|entry| entry.path().is_dir())
+        .filter_map(|entry| entry.file_name().into_string().ok())
+        .sorted()
+        .map(CompletionCandidate::new)
+        .collect()
+}
+
+/// Find the venv name that was already typed after the subcommand (or one of its aliases),
+/// e.g. `uvenv uninject <venv> ...`.
+fn venv_from_args(subcommands: &[&str]) -> Option<String> {
+    // args look like `uvenv -- uvenv uninject <venv> <incomplete>`
+    env::args()
+        .skip_while(|arg| !subcommands.contains(&arg.as_str()))
+        .nth(1)
+}
+
+/// Read a venv's metadata without async (see module docs).
+fn load_metadata_sync(venv_name: &str) -> Option<Metadata> {
+    let mut buf = std::fs::read(venv_path(venv_name).join(".metadata")).ok()?;
+    strip_header(&mut buf);
+
+    rmp_serde::decode::from_slice(&buf).ok()
+}
+
+/// Packages injected into the venv given to `uvenv uninject` (or `uvenv eject`).
+pub fn injected_packages() -> Vec<CompletionCandidate> {
+    venv_from_args(&["uninject", "eject"])
+        .and_then(|venv_name| load_metadata_sync(&venv_name))
+        .map(|meta| {
+            meta.injected
+                .into_iter()
+                .sorted()
+                .map(CompletionCandidate::new)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Scripts provided by the venv given to `uvenv exec`.
+pub fn venv_scripts() -> Vec<CompletionCandidate> {
+    venv_from_args(&["exec"])
+        .and_then(|venv_name| load_metadata_sync(&venv_name))
+        .map(|meta| {
+            meta.scripts
+                .into_keys()
+                .map(CompletionCandidate::new)
+                .collect()
+        })
+        .unwrap_or_default()
+}
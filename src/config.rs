@@ -0,0 +1,67 @@
+use anyhow::Context;
+use serde::Deserialize;
+use std::io;
+use std::path::PathBuf;
+
+use crate::metadata::get_work_dir;
+
+const OSV_PYPI_URL: &str = "https://osv-vulnerabilities.storage.googleapis.com/PyPI/all.zip";
+
+/// `~/.local/uvenv/config.toml`, e.g.
+///
+///     [audit]
+///     database = "/srv/osv/pypi"
+///
+///     [licenses]
+///     denylist = ["AGPL", "SSPL"]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Deserialize)]
+#[serde(default, rename_all = "kebab-case")]
+pub struct Config {
+    pub audit: AuditConfig,
+    pub licenses: LicensesConfig,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Deserialize)]
+#[serde(default, rename_all = "kebab-case")]
+pub struct AuditConfig {
+    /// directory with OSV advisories (json)
+    pub database: Option<PathBuf>,
+    /// zip file with OSV advisories, used by `uvenv audit update-db`
+    pub database_url: Option<String>,
+}
+
+impl AuditConfig {
+    pub fn database_path(&self) -> PathBuf {
+        self.database
+            .clone()
+            .unwrap_or_else(|| get_work_dir().join("advisories"))
+    }
+
+    pub fn database_url(&self) -> &str {
+        self.database_url.as_deref().unwrap_or(OSV_PYPI_URL)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Deserialize)]
+#[serde(default, rename_all = "kebab-case")]
+pub struct LicensesConfig {
+    /// licenses to flag in `uvenv licenses` (case-insensitive substring match, e.g. 'AGPL')
+    pub denylist: Vec<String>,
+}
+
+pub fn config_path() -> PathBuf {
+    get_work_dir().join("config.toml")
+}
+
+pub async fn load_config() -> anyhow::Result<Config> {
+    let path = config_path();
+
+    let content = match tokio::fs::read_to_string(&path).await {
+        Ok(content) => content,
+        // no config = defaults
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Config::default()),
+        Err(err) => return Err(err.into()),
+    };
+
+    toml::from_str(&content).with_context(|| format!("Invalid config file {path:?}"))
+}
@@ -0,0 +1,174 @@
+//! Typed errors for failures that callers (e.g. wrapper scripts) may want to handle differently.
+//! Everything is still passed around as `anyhow::Error`; `main` looks for a `UvenvError` in the chain
+//! to decide the exit code and the `code` in `--output json`.
+use core::fmt::{Display, Formatter};
+use owo_colors::OwoColorize;
+
+/// Exit code for failures without a more specific `UvenvError`.
+pub const GENERIC_EXIT_CODE: i32 = 1;
+
+/// Exit code of `uvenv audit` when vulnerabilities were found.
+/// Not a failure (so not a `UvenvError`), but it can't collide with the codes below.
+pub const VULNERABILITIES_EXIT_CODE: i32 = 11;
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum UvenvError {
+    /// name of the venv
+    VenvNotFound(String),
+    /// name of the venv
+    AlreadyInstalled(String),
+    /// name of the script
+    ScriptConflict(String),
+    /// requested python version or executable
+    InterpreterMissing(String),
+    /// the command that failed (its stderr is the cause of this error)
+    ResolutionFailed(String),
+    /// what could not be reached
+    NetworkError(String),
+    /// path of the metadata file
+    MetadataCorrupt(String),
+    /// the command that failed (its stderr is the cause of this error)
+    CommandFailed(String),
+    Interrupted,
+    Terminated,
+}
+
+impl UvenvError {
+    /// Stable, machine-readable identifier (used in JSON output).
+    pub const fn code(&self) -> &'static str {
+        match self {
+            Self::VenvNotFound(_) => "venv-not-found",
+            Self::AlreadyInstalled(_) => "already-installed",
+            Self::ScriptConflict(_) => "script-conflict",
+            Self::InterpreterMissing(_) => "interpreter-missing",
+            Self::ResolutionFailed(_) => "resolution-failed",
+            Self::NetworkError(_) => "network-error",
+            Self::MetadataCorrupt(_) => "metadata-corrupt",
+            Self::CommandFailed(_) => "command-failed",
+            Self::Interrupted => "interrupted",
+            Self::Terminated => "terminated",
+        }
+    }
+
+    /// Exit codes are part of the public interface, don't change them!
+    /// Signals follow the shell convention of 128 + signal.
+    /// 11 is taken by `VULNERABILITIES_EXIT_CODE`.
+    pub const fn exit_code(&self) -> i32 {
+        match self {
+            Self::VenvNotFound(_) => 3,
+            Self::AlreadyInstalled(_) => 4,
+            Self::ScriptConflict(_) => 5,
+            Self::InterpreterMissing(_) => 6,
+            Self::ResolutionFailed(_) => 7,
+            Self::NetworkError(_) => 8,
+            Self::MetadataCorrupt(_) => 9,
+            Self::CommandFailed(_) => 10,
+            Self::Interrupted => 130,
+            Self::Terminated => 143,
+        }
+    }
+
+    /// Failures that may succeed when simply tried again.
+    pub const fn is_retryable(&self) -> bool {
+        matches!(self, Self::NetworkError(_))
+    }
+
+    /// Classify a failed command (usually `uv`) based on its stderr.
+    /// This is best-effort: uv's messages aren't a stable interface (these match uv 0.4.25, see `tests/errors.rs`),
+    /// anything unrecognized is reported as `CommandFailed`.
+    pub fn from_stderr(
+        command: &str,
+        stderr: &str,
+    ) -> Self {
+        let command = command.to_owned();
+
+        // uv's resolver errors start with this, 'Because ...' alone also shows up in other errors:
+        if stderr.contains("No solution found when resolving") {
+            Self::ResolutionFailed(command)
+        } else if stderr.contains("Failed to fetch")
+            || stderr.contains("error sending request")
+            || stderr.contains("dns error")
+            || stderr.contains("Network is unreachable")
+        {
+            Self::NetworkError(command)
+        } else if stderr.contains("No interpreter found") {
+            Self::InterpreterMissing(command)
+        } else {
+            Self::CommandFailed(command)
+        }
+    }
+}
+
+impl Display for UvenvError {
+    fn fmt(
+        &self,
+        f: &mut Formatter<'_>,
+    ) -> core::fmt::Result {
+        match self {
+            Self::VenvNotFound(name) => write!(f, "No virtualenv for '{}'.", name.green()),
+            Self::AlreadyInstalled(name) => write!(
+                f,
+                "'{}' is already installed.\nUse '{}' to update existing tools or pass '{}' to this command to ignore this message.",
+                name.green(),
+                "uvenv upgrade".green(),
+                "--force".green()
+            ),
+            Self::ScriptConflict(script) => write!(
+                f,
+                "Script {} already exists. Use --force to ignore this warning.",
+                script.yellow()
+            ),
+            Self::InterpreterMissing(python) => {
+                write!(f, "Python '{}' could not be found.", python.red())
+            },
+            Self::ResolutionFailed(command) => {
+                write!(f, "Could not resolve the requirements ({command}).")
+            },
+            Self::NetworkError(target) => write!(f, "Network error while contacting {target}."),
+            Self::MetadataCorrupt(path) => write!(f, "Metadata file {} is corrupt.", path.red()),
+            Self::CommandFailed(command) => write!(f, "{command} failed"),
+            Self::Interrupted => write!(f, "Interrupted by Ctrl-C."),
+            Self::Terminated => write!(f, "Terminated."),
+        }
+    }
+}
+
+impl core::error::Error for UvenvError {}
+
+/// Output of a failed command, kept as its own error so its stderr isn't mixed into our messages.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CommandError {
+    pub exit_code: i32,
+    pub stderr: String,
+}
+
+impl Display for CommandError {
+    fn fmt(
+        &self,
+        f: &mut Formatter<'_>,
+    ) -> core::fmt::Result {
+        let stderr = self.stderr.trim();
+        if stderr.is_empty() {
+            write!(f, "exited with code {}", self.exit_code)
+        } else {
+            write!(f, "{stderr}")
+        }
+    }
+}
+
+impl core::error::Error for CommandError {}
+
+/// The most specific `UvenvError` in an error chain (if any).
+pub fn find_uvenv_error(err: &anyhow::Error) -> Option<&UvenvError> {
+    err.chain()
+        .find_map(|cause| cause.downcast_ref::<UvenvError>())
+}
+
+pub fn find_command_error(err: &anyhow::Error) -> Option<&CommandError> {
+    err.chain()
+        .find_map(|cause| cause.downcast_ref::<CommandError>())
+}
+
+pub fn exit_code_for(err: &anyhow::Error) -> i32 {
+    find_uvenv_error(err).map_or(GENERIC_EXIT_CODE, UvenvError::exit_code)
+}
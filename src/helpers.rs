@@ -80,3 +80,55 @@ pub const fn flatten_option_ref<T>(nested: Option<&Option<T>>) -> Option<&T> {
         _ => None,
     }
 }
+
+/// Total size (in bytes) of all files in a directory, without following symlinks.
+pub fn dir_size(path: &Path) -> u64 {
+    let mut total = 0;
+    let mut todo = vec![path.to_path_buf()];
+
+    while let Some(dir) = todo.pop() {
+        let Ok(entries) = std::fs::read_dir(&dir) else {
+            continue;
+        };
+
+        for entry in entries.flatten() {
+            let Ok(meta) = entry.metadata() else {
+                continue;
+            };
+
+            if meta.is_dir() {
+                todo.push(entry.path());
+            } else {
+                total += meta.len();
+            }
+        }
+    }
+
+    total
+}
+
+/// e.g. 1536 -> '1.5 KiB'
+#[expect(
+    clippy::as_conversions,
+    clippy::cast_precision_loss,
+    reason = "Only used for display purposes"
+)]
+pub fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+
+    let mut size = bytes as f64 / 1024.0;
+    let mut unit = "KiB";
+    for next_unit in UNITS.iter().skip(1) {
+        if size < 1024.0 {
+            break;
+        }
+        size /= 1024.0;
+        unit = next_unit;
+    }
+
+    format!("{size:.1} {unit}")
+}
@@ -2,11 +2,19 @@ mod animate;
 mod cli;
 mod cmd;
 mod commands;
+mod complete;
+mod config;
+mod errors;
 mod helpers;
 mod metadata;
+mod offline;
+mod output;
+mod pep723;
 mod pip;
 mod promises;
 mod pypi;
+mod run_cache;
+mod shells;
 mod symlinks;
 mod tests;
 mod uv;
@@ -15,12 +23,16 @@ mod venv;
 use std::io;
 
 use clap::{Command, CommandFactory, Parser};
-use clap_complete::{generate, Generator, Shell};
+use clap_complete::{generate, CompleteEnv, Generator, Shell};
 
 use crate::cli::{Args, Process};
 use crate::commands::activate::generate_activate;
 use crate::commands::ensurepath::ensure_path_generate;
+use crate::errors::exit_code_for;
 use crate::helpers::fmt_error;
+use crate::offline::set_offline;
+use crate::output::{is_json, print_error_json, set_output_format};
+use crate::shells::SupportedShell;
 use std::process::exit;
 
 pub fn print_completions<G: Generator>(
@@ -31,47 +43,59 @@ pub fn print_completions<G: Generator>(
     generate(gen, cmd, cmd.get_name().to_owned(), &mut io::stdout());
 }
 
-pub async fn generate_bash(generator: Shell) {
+pub async fn generate_shell(shell: SupportedShell) {
     let mut cmd = Args::command();
 
     let args = cmd.clone().get_matches();
     match args.subcommand_name() {
         Some("activate") => {
             // generate code for uvenv activate
-            println!("{}", generate_activate().await);
+            println!("{}", generate_activate(shell).await);
         },
         Some("ensurepath") => {
             // geneate code for uvenv ensurepath
-            println!("{}", ensure_path_generate().await);
+            println!("{}", ensure_path_generate(shell).await);
+        },
+        Some("completions") => {
+            // load the dynamic completions, so existing `eval "$(uvenv --generate=bash completions)"` lines keep working
+            println!("{}", shell.completion_line());
         },
         _ => {
-            // other cases: show regular completions
-            print_completions(generator, &mut cmd);
-            // todo: dynamic completions for e.g. `uvenv upgrade <venv>`
+            // other cases: show regular (static) completions
+            print_completions(shell.generator(), &mut cmd);
         },
     }
 }
 
 pub async fn generate_code(target: Shell) -> i32 {
-    if target == Shell::Bash {
-        generate_bash(target).await;
+    if let Some(shell) = SupportedShell::from_generator(target) {
+        generate_shell(shell).await;
         0
     } else {
-        eprintln!("Error: only 'bash' is supported at this moment.");
+        eprintln!("Error: only 'bash', 'zsh' and 'fish' are supported at this moment.");
         126
     }
 }
 
 #[tokio::main]
 async fn main() {
+    // handles `COMPLETE=<shell> uvenv ...` (and exits), otherwise does nothing:
+    CompleteEnv::with_factory(Args::command).complete();
+
     let args = Args::parse();
+    set_output_format(args.output);
+    set_offline(args.offline);
 
     let exit_code = if let Some(generator) = args.generator {
         generate_code(generator).await
     } else {
         args.cmd.process().await.unwrap_or_else(|msg| {
-            eprintln!("{}", fmt_error(&msg));
-            1
+            if is_json() {
+                print_error_json(&msg);
+            } else {
+                eprintln!("{}", fmt_error(&msg));
+            }
+            exit_code_for(&msg)
         })
     };
 
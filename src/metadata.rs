@@ -1,7 +1,11 @@
-use crate::pypi::get_latest_version;
+use crate::errors::UvenvError;
+use crate::helpers::PathAsStr;
+use crate::pypi::{find_yanked_releases, get_latest_version, YankedRelease};
 use crate::symlinks::check_symlink;
-use crate::uv::{uv_get_installed_version, uv_venv, Helpers};
-use anyhow::anyhow;
+use crate::uv::{
+    environment_from_root, uv_get_installed_version, uv_installed_packages, uv_venv, Helpers,
+};
+use anyhow::{anyhow, Context};
 use core::cmp::Ordering;
 use core::fmt::Write;
 use core::str::FromStr;
@@ -15,7 +19,7 @@ use tokio::fs::{create_dir_all, File};
 use tokio::io::AsyncReadExt;
 use tokio::io::AsyncWriteExt;
 use uv_pep440::{Version, VersionSpecifier};
-use uv_pep508::Requirement;
+use uv_pep508::{PackageName, Requirement};
 use uv_python::PythonEnvironment;
 
 const BIN_DIR: &str = ".local/bin";
@@ -65,10 +69,49 @@ pub fn get_venv_dir() -> PathBuf {
     work_dir.join("venvs")
 }
 
+/// Cached environments for `uvenv run`
+pub fn get_run_dir() -> PathBuf {
+    let work_dir = get_work_dir();
+    work_dir.join("runs")
+}
+
 pub fn venv_path(venv_name: &str) -> PathBuf {
     get_venv_dir().join(venv_name)
 }
 
+/// Venv directories without a `.metadata` file (which `list_packages` can't load).
+pub fn find_venvs_without_metadata(filter_names: &[String]) -> Vec<String> {
+    let Ok(venv_dir) = std::fs::read_dir(get_venv_dir()) else {
+        return Vec::new();
+    };
+
+    venv_dir
+        .flatten()
+        .filter(|entry| entry.path().is_dir() && !entry.path().join(".metadata").exists())
+        .filter_map(|entry| entry.file_name().into_string().ok())
+        .filter(|venv_name| filter_names.is_empty() || filter_names.contains(venv_name))
+        .sorted()
+        .collect()
+}
+
+/// Directories in the venv dir that are neither a uvenv venv nor a virtualenv at all
+/// (e.g. leftovers of an install that crashed before the venv was created).
+/// Unlike venvs without metadata, these can't be repaired by `uvenv check --fix`.
+pub fn find_orphaned_venv_dirs() -> Vec<PathBuf> {
+    let Ok(venv_dir) = std::fs::read_dir(get_venv_dir()) else {
+        return Vec::new();
+    };
+
+    venv_dir
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.is_dir() && !path.join(".metadata").exists() && !path.join("pyvenv.cfg").exists()
+        })
+        .sorted()
+        .collect()
+}
+
 pub fn version_0() -> Version {
     Version::from_str("0.0.0").expect("Version 0.0.0 should be parseable.")
 }
@@ -95,6 +138,10 @@ pub struct Metadata {
     pub available_version: String,
     #[serde(default)]
     pub outdated: bool,
+    /// installed releases (the package itself and its dependencies) that were yanked from the index.
+    /// Only looked up at runtime (see `LoadMetadataConfig::yanked_check`), never stored in `.metadata`.
+    #[serde(skip)]
+    pub yanked: Vec<YankedRelease>,
 }
 
 impl PartialOrd for Metadata {
@@ -130,6 +177,7 @@ impl Metadata {
             editable: false,
             available_version: String::new(),
             outdated: false,
+            yanked: Vec::new(),
         }
     }
 
@@ -206,48 +254,46 @@ impl Metadata {
     ) -> anyhow::Result<Self> {
         let meta_path = dirname.join(".metadata");
 
-        Self::for_file(&meta_path, config).await.map_or_else(
-            || {
-                let venv_name = dirname
-                    .file_name()
-                    .and_then(|fname| fname.to_str())
-                    .unwrap_or_default();
-
-                Err(anyhow!(
-                    "Metadata for '{}' could not be loaded.",
-                    venv_name.red()
-                ))
-            },
-            Ok,
-        )
+        Self::for_file(&meta_path, config).await.with_context(|| {
+            let venv_name = dirname
+                .file_name()
+                .and_then(|fname| fname.to_str())
+                .unwrap_or_default();
+
+            format!("Metadata for '{}' could not be loaded.", venv_name.red())
+        })
     }
 
     pub async fn for_dir(
         dirname: &Path,
         config: &LoadMetadataConfig,
-    ) -> Option<Self> {
+    ) -> anyhow::Result<Self> {
         let meta_path = dirname.join(".metadata");
 
         Self::for_file(&meta_path, config).await
     }
 
+    /// Falls back to `Metadata::find` if the venv has no `.metadata` file,
+    /// but a file that can't be read (e.g. `MetadataCorrupt`) is an error.
     pub async fn for_requirement(
         requirement: &Requirement,
         config: &LoadMetadataConfig,
-    ) -> Self {
+    ) -> anyhow::Result<Self> {
         let requirement_name = requirement.name.to_string();
         let venv_dir = venv_path(&requirement_name);
 
-        Self::for_dir(&venv_dir, config)
-            .await
-            .map_or_else(|| Self::find(requirement), |meta| meta)
+        if venv_dir.join(".metadata").exists() {
+            Self::for_dir(&venv_dir, config).await
+        } else {
+            Ok(Self::find(requirement))
+        }
     }
 
     pub async fn for_file(
         filename: &Path,
         config: &LoadMetadataConfig,
-    ) -> Option<Self> {
-        load_metadata(filename, config).await.ok()
+    ) -> anyhow::Result<Self> {
+        load_metadata(filename, config).await
     }
 
     pub async fn save(
@@ -277,6 +323,49 @@ impl Metadata {
         }
     }
 
+    /// Look up every package installed in the venv and remember the yanked ones.
+    pub async fn check_yanked(
+        &mut self,
+        venv_path: &Path,
+    ) {
+        let Ok(environ) = environment_from_root(venv_path) else {
+            return;
+        };
+        let Ok(installed) = uv_installed_packages(&environ) else {
+            return;
+        };
+
+        let packages = installed
+            .into_iter()
+            .filter_map(|(name, package)| {
+                Version::from_str(&package.version)
+                    .ok()
+                    .map(|version| (name, version))
+            })
+            .collect();
+
+        self.yanked = find_yanked_releases(packages).await;
+    }
+
+    /// The yanked release of the main package (not one of its dependencies), if any.
+    pub fn yanked_self(&self) -> Option<&YankedRelease> {
+        let name = PackageName::from_str(&self.name).ok()?;
+
+        self.yanked
+            .iter()
+            .find(|release| PackageName::from_str(&release.name).is_ok_and(|other| other == name))
+    }
+
+    /// Yanked releases of dependencies (including injected packages).
+    pub fn yanked_dependencies(&self) -> Vec<&YankedRelease> {
+        let main = self.yanked_self();
+
+        self.yanked
+            .iter()
+            .filter(|release| Some(*release) != main)
+            .collect()
+    }
+
     pub async fn check_scripts(
         &mut self,
         venv_path: &Path,
@@ -296,7 +385,7 @@ impl Metadata {
     }
 
     pub fn format_installed_version(&self) -> String {
-        if self.outdated {
+        if self.outdated || self.yanked_self().is_some() {
             self.installed_version.red().to_string()
         } else {
             self.installed_version.cyan().to_string()
@@ -309,7 +398,16 @@ impl Metadata {
     }
 
     pub fn format_short(&self) -> String {
-        format!("- {} {}", self.name, self.format_installed_version())
+        if self.yanked.is_empty() {
+            format!("- {} {}", self.name, self.format_installed_version())
+        } else {
+            format!(
+                "- {} {} {}",
+                self.name,
+                self.format_installed_version(),
+                "(yanked)".red()
+            )
+        }
     }
 
     #[expect(dead_code, reason = "Useful variant of `vec_injected`")]
@@ -384,6 +482,29 @@ impl Metadata {
             )?;
         }
 
+        if let Some(release) = self.yanked_self() {
+            let reason = if release.reason.is_empty() {
+                "no reason given"
+            } else {
+                release.reason.as_str()
+            };
+            writeln!(
+                result,
+                "{INDENT}{} {}",
+                "Yanked:".red().bold(),
+                reason.yellow()
+            )?;
+        }
+
+        let yanked_dependencies = self.yanked_dependencies();
+        if !yanked_dependencies.is_empty() {
+            let formatted_yanked = yanked_dependencies
+                .iter()
+                .map(|release| release.to_string().red().to_string())
+                .join(", ");
+            writeln!(result, "{INDENT}Yanked Dependencies: {formatted_yanked}")?;
+        }
+
         if !self.injected.is_empty() {
             let formatted_injects = self.format_injected();
             // result.push_str(&format!("{INDENT}Injected Packages: {formatted_injects}\n"));
@@ -450,7 +571,9 @@ pub async fn load_generic_msgpack<'buf, T: serde::Deserialize<'buf>>(
     strip_header(buf);
 
     // Read the contents of the file into a Metadata struct
-    let metadata: T = rmp_serde::decode::from_slice(buf)?;
+    let metadata: T = rmp_serde::decode::from_slice(buf).map_err(|err| {
+        anyhow!(err).context(UvenvError::MetadataCorrupt(filename.as_str().to_owned()))
+    })?;
 
     Ok(metadata)
 }
@@ -462,6 +585,8 @@ pub struct LoadMetadataConfig {
     pub updates_check: bool,
     pub updates_prereleases: bool,
     pub updates_ignore_constraints: bool,
+    /// look up every installed package on the index (slow, so only for `check` or `list --check-yanked`)
+    pub yanked_check: bool,
 }
 
 impl LoadMetadataConfig {
@@ -472,6 +597,7 @@ impl LoadMetadataConfig {
             updates_check: true,
             updates_prereleases: true,
             updates_ignore_constraints: true,
+            yanked_check: true,
         }
     }
 
@@ -481,6 +607,7 @@ impl LoadMetadataConfig {
             updates_check: false,
             updates_prereleases: false,
             updates_ignore_constraints: false,
+            yanked_check: false,
         }
     }
 }
@@ -492,6 +619,7 @@ impl Default for LoadMetadataConfig {
             updates_check: true,
             updates_prereleases: false,
             updates_ignore_constraints: false,
+            yanked_check: false,
         }
     }
 }
@@ -519,6 +647,10 @@ pub async fn load_metadata(
                 )
                 .await;
         }
+
+        if config.yanked_check {
+            metadata.check_yanked(folder).await;
+        }
     }
 
     Ok(metadata)
@@ -0,0 +1,29 @@
+//! Global `--offline` setting (also `UVENV_OFFLINE`): never touch the network, use cached data instead.
+use std::env;
+use std::sync::OnceLock;
+use uv_client::Connectivity;
+
+static OFFLINE: OnceLock<bool> = OnceLock::new();
+
+/// Should be called once, right after parsing the cli args.
+pub fn set_offline(offline: bool) {
+    if offline {
+        // picked up by every `uv` subprocess, equivalent to passing `--offline` to each of them:
+        env::set_var("UV_OFFLINE", "1");
+    }
+
+    let _ = OFFLINE.set(offline);
+}
+
+pub fn is_offline() -> bool {
+    OFFLINE.get().copied().unwrap_or_default()
+}
+
+/// Connectivity for uv's (registry) clients.
+pub fn connectivity() -> Connectivity {
+    if is_offline() {
+        Connectivity::Offline
+    } else {
+        Connectivity::Online
+    }
+}
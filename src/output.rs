@@ -0,0 +1,166 @@
+//! Global `--output` setting and the structured results of commands that change venvs.
+use core::fmt::{Display, Formatter};
+use regex::Regex;
+use serde::Serialize;
+use std::sync::OnceLock;
+
+use crate::cli::OutputFormat;
+use crate::errors::{exit_code_for, find_command_error, find_uvenv_error, UvenvError};
+use crate::symlinks::ScriptChanges;
+
+static OUTPUT_FORMAT: OnceLock<OutputFormat> = OnceLock::new();
+
+/// Should be called once, right after parsing the cli args.
+pub fn set_output_format(format: OutputFormat) {
+    let _ = OUTPUT_FORMAT.set(format);
+}
+
+pub fn output_format() -> OutputFormat {
+    OUTPUT_FORMAT.get().copied().unwrap_or_default()
+}
+
+pub fn is_json() -> bool {
+    output_format() == OutputFormat::Json
+}
+
+/// Messages are colored for the terminal, which is just noise in JSON.
+pub fn strip_ansi(text: &str) -> String {
+    static ANSI: OnceLock<Regex> = OnceLock::new();
+    let ansi = ANSI
+        .get_or_init(|| Regex::new(r"\x1b\[[0-9;]*m").expect("ANSI escape regex should be valid."));
+
+    ansi.replace_all(text, "").into_owned()
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Action {
+    Install,
+    Create,
+    Upgrade,
+    Uninstall,
+    Reinstall,
+    Inject,
+    Uninject,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+pub struct ActionResult {
+    pub action: Action,
+    pub venv: String,
+    /// empty if the venv didn't exist before
+    pub old_version: String,
+    /// empty if the venv was removed
+    pub new_version: String,
+    pub scripts_linked: Vec<String>,
+    pub scripts_unlinked: Vec<String>,
+    pub warnings: Vec<String>,
+    pub errors: Vec<String>,
+    /// see `UvenvError::code`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error_code: Option<&'static str>,
+    /// human-readable version of this result
+    #[serde(skip)]
+    pub message: String,
+}
+
+impl ActionResult {
+    pub fn new(
+        action: Action,
+        venv: &str,
+    ) -> Self {
+        Self {
+            action,
+            venv: venv.to_owned(),
+            old_version: String::new(),
+            new_version: String::new(),
+            scripts_linked: Vec::new(),
+            scripts_unlinked: Vec::new(),
+            warnings: Vec::new(),
+            errors: Vec::new(),
+            error_code: None,
+            message: String::new(),
+        }
+    }
+
+    /// Result for one venv of an `*-all` command that failed.
+    pub fn failed(
+        action: Action,
+        venv: &str,
+        err: &anyhow::Error,
+    ) -> Self {
+        let mut result = Self::new(action, venv);
+        result.errors.push(strip_ansi(&format!("{err:#}")));
+        result.error_code = find_uvenv_error(err).map(UvenvError::code);
+        result
+    }
+
+    pub fn with_message<S: Into<String>>(
+        mut self,
+        message: S,
+    ) -> Self {
+        self.message = message.into();
+        self
+    }
+
+    pub fn with_changes(
+        mut self,
+        changes: &ScriptChanges,
+    ) -> Self {
+        self.scripts_linked.clone_from(&changes.linked);
+        self.scripts_unlinked.clone_from(&changes.unlinked);
+        self.message.push_str(&changes.format_human());
+        self
+    }
+
+    pub fn warn<S: AsRef<str>>(
+        &mut self,
+        warning: S,
+    ) {
+        self.warnings.push(strip_ansi(warning.as_ref()));
+    }
+
+    /// Print the message, or the result as JSON with `--output json`.
+    pub fn print(&self) -> anyhow::Result<()> {
+        if is_json() {
+            println!("{}", serde_json::to_string_pretty(self)?);
+        } else {
+            println!("{}", self.message);
+        }
+
+        Ok(())
+    }
+}
+
+impl Display for ActionResult {
+    fn fmt(
+        &self,
+        f: &mut Formatter<'_>,
+    ) -> core::fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+/// Results of an `*-all` command are printed as they come in,
+/// except with `--output json`: then they're printed at the end as a single array (including failures).
+pub fn print_results_json(results: &[ActionResult]) -> anyhow::Result<()> {
+    println!("{}", serde_json::to_string_pretty(results)?);
+    Ok(())
+}
+
+/// With `--output json`, errors are printed to stdout as a result object too, so scripts only have to parse stdout.
+pub fn print_error_json(err: &anyhow::Error) {
+    let errors = [strip_ansi(&format!("{err:#}"))];
+    let uvenv_error = find_uvenv_error(err);
+
+    let json = serde_json::json!({
+        "errors": errors,
+        "code": uvenv_error.map_or("error", UvenvError::code),
+        "exit_code": exit_code_for(err),
+        "retryable": uvenv_error.is_some_and(UvenvError::is_retryable),
+        // output of the command that failed (e.g. uv), if any:
+        "stderr": find_command_error(err).map(|cmd_err| cmd_err.stderr.clone()),
+    });
+
+    println!("{json:#}");
+}
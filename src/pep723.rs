@@ -0,0 +1,91 @@
+use anyhow::{bail, Context};
+use serde::Deserialize;
+use std::path::Path;
+
+const BLOCK_START: &str = "# /// script";
+const BLOCK_END: &str = "# ///";
+
+/// Inline script metadata (PEP 723), e.g.
+///
+///     # /// script
+///     # requires-python = ">=3.11"
+///     # dependencies = ["requests<3", "rich"]
+///     # ///
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct ScriptMetadata {
+    #[serde(default)]
+    pub dependencies: Vec<String>,
+    pub requires_python: Option<String>,
+}
+
+/// Extract the (toml) contents of the `# /// script` block from Python source code.
+/// Follows the reference implementation: a block ends at the last `# ///` line of a run of comments.
+pub fn extract_script_block(source: &str) -> anyhow::Result<Option<String>> {
+    let mut blocks = Vec::new();
+    // (content lines, amount of lines before the most recent `# ///`)
+    let mut current: Option<(Vec<&str>, Option<usize>)> = None;
+
+    // extra empty line to close a block at the end of the file
+    for line in source.lines().chain(core::iter::once("")) {
+        let Some((mut content, mut closed_at)) = current.take() else {
+            if line == BLOCK_START {
+                current = Some((Vec::new(), None));
+            }
+            continue;
+        };
+
+        if line == BLOCK_END {
+            closed_at = Some(content.len());
+            content.push("///"); // only content if more comment lines follow
+            current = Some((content, closed_at));
+            continue;
+        }
+
+        let text = if line == "#" {
+            Some("")
+        } else {
+            line.strip_prefix("# ")
+        };
+
+        if let Some(text) = text {
+            content.push(text);
+            current = Some((content, closed_at));
+            continue;
+        }
+
+        // end of the comment block:
+        if let Some(end) = closed_at {
+            content.truncate(end);
+            blocks.push(content.join("\n"));
+        }
+    }
+
+    if blocks.len() > 1 {
+        bail!("Multiple `script` blocks found, only one is allowed.");
+    }
+
+    Ok(blocks.pop())
+}
+
+pub fn parse_script_metadata(source: &str) -> anyhow::Result<ScriptMetadata> {
+    let Some(block) = extract_script_block(source)? else {
+        // no inline metadata = no dependencies
+        return Ok(ScriptMetadata::default());
+    };
+
+    toml::from_str(&block).with_context(|| "Invalid `script` metadata block")
+}
+
+pub async fn read_script_metadata(script: &Path) -> anyhow::Result<ScriptMetadata> {
+    let source = tokio::fs::read_to_string(script)
+        .await
+        .with_context(|| format!("Could not read script {script:?}"))?;
+
+    parse_script_metadata(&source)
+}
+
+/// Should `uvenv run <spec>` treat `spec` as a script instead of a package?
+pub fn is_script(spec: &str) -> bool {
+    spec.ends_with(".py") && Path::new(spec).is_file()
+}
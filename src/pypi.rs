@@ -1,12 +1,17 @@
+use crate::offline::{connectivity, is_offline};
 use crate::pip::parse_requirement;
 use crate::uv::uv_cache;
+use core::fmt;
 use rkyv::{deserialize, Archive, Archived, Deserialize};
+use serde::Serialize;
 use uv_pep440::{Version, VersionSpecifier};
 use uv_pep508::{PackageName, Requirement};
 use uv_pypi_types::Yanked;
 
+use futures::{future, stream, StreamExt};
 use rkyv::api::high::HighDeserializer;
 use std::collections::HashSet;
+use std::sync::OnceLock;
 use uv_client::{
     OwnedArchive, RegistryClient, RegistryClientBuilder, SimpleMetadata, SimpleMetadatum,
     VersionFiles,
@@ -35,12 +40,24 @@ impl Default for SimplePypi {
     /// Create a (default) Registry
     fn default() -> Self {
         let cache = uv_cache();
-        let inner = RegistryClientBuilder::new(cache).build();
+        let inner = RegistryClientBuilder::new(cache)
+            .connectivity(connectivity())
+            .build();
 
         Self(inner)
     }
 }
 
+/// Shared by all lookups, so connections and the http cache are reused.
+static REGISTRY: OnceLock<SimplePypi> = OnceLock::new();
+
+fn registry() -> &'static SimplePypi {
+    REGISTRY.get_or_init(SimplePypi::default)
+}
+
+/// How many packages are looked up on the index at the same time (per venv).
+const LOOKUP_CONCURRENCY: usize = 8;
+
 /// usage: e.g. `let x: Option<VersionFiles> = deserialize(&metadatum.files);`
 /// Note: pycharm will probably complain, but it WILL work for `ArchivedSimpleMetadatum`!
 pub fn rkyv_deserialize<T>(archived: &Archived<T>) -> Option<T>
@@ -89,6 +106,105 @@ fn find_non_yanked_versions(metadata: &OwnedArchive<SimpleMetadata>) -> HashSet<
     valid_versions
 }
 
+/// An installed release that was yanked from the package index.
+#[derive(
+    Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize, serde::Deserialize,
+)]
+pub struct YankedRelease {
+    pub name: String,
+    pub version: String,
+    /// empty if the index doesn't provide one
+    #[serde(default)]
+    pub reason: String,
+}
+
+impl fmt::Display for YankedRelease {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        write!(f, "{}=={}", self.name, self.version)?;
+        if !self.reason.is_empty() {
+            write!(f, " ({})", self.reason)?;
+        }
+        Ok(())
+    }
+}
+
+/// A release only counts as yanked if all of its files are (PEP 592).
+/// Returns the yank reason (empty if none was given), or `None` if the release is not yanked.
+pub fn release_yank_reason(files: &[Option<Yanked>]) -> Option<String> {
+    if files.is_empty() || !files.iter().all(is_yanked) {
+        return None;
+    }
+
+    let reason = files
+        .iter()
+        .find_map(|yanked| {
+            if let Some(Yanked::Reason(reason)) = yanked {
+                Some(reason.to_string())
+            } else {
+                None
+            }
+        })
+        .unwrap_or_default();
+
+    Some(reason)
+}
+
+fn find_yank_reason(
+    metadata: &OwnedArchive<SimpleMetadata>,
+    version: &Version,
+) -> Option<String> {
+    let metadatum = metadata.iter().find(|metadatum| {
+        rkyv_deserialize(&metadatum.version).is_some_and(|candidate: Version| candidate == *version)
+    })?;
+    let files: VersionFiles = rkyv_deserialize(&metadatum.files)?;
+
+    let yanked: Vec<Option<Yanked>> = files
+        .source_dists
+        .into_iter()
+        .map(|source_dist| source_dist.file.yanked)
+        .chain(files.wheels.into_iter().map(|wheel| wheel.file.yanked))
+        .collect();
+
+    release_yank_reason(&yanked)
+}
+
+/// The yank reason if this version of the package was yanked from the index.
+/// Lookup errors are ignored (e.g. offline or a package that's not on the index).
+pub async fn get_yank_reason(
+    package_name: &PackageName,
+    version: &Version,
+) -> Option<String> {
+    let data = registry().lookup(package_name).await.ok()?;
+    let (_, metadata) = data.iter().next_back()?;
+
+    find_yank_reason(metadata, version)
+}
+
+/// All yanked releases among the given (name, version) pairs, a few lookups at a time.
+pub async fn find_yanked_releases(packages: Vec<(PackageName, Version)>) -> Vec<YankedRelease> {
+    let mut yanked: Vec<YankedRelease> = stream::iter(packages)
+        .map(|(name, version)| async move {
+            get_yank_reason(&name, &version)
+                .await
+                .map(|reason| YankedRelease {
+                    name: name.to_string(),
+                    version: version.to_string(),
+                    reason,
+                })
+        })
+        .buffer_unordered(LOOKUP_CONCURRENCY)
+        .filter_map(future::ready)
+        .collect()
+        .await;
+
+    // `buffer_unordered` yields in order of completion:
+    yanked.sort();
+    yanked
+}
+
 pub async fn get_versions_for_packagename(
     package_name: &PackageName,
     stable: bool,
@@ -96,11 +212,12 @@ pub async fn get_versions_for_packagename(
 ) -> Vec<Version> {
     let mut versions: Vec<Version> = vec![];
 
-    let client = SimplePypi::default();
-
-    let data = match client.lookup(package_name).await {
+    let data = match registry().lookup(package_name).await {
         Err(err) => {
-            eprintln!("Something went wrong: {err};");
+            // offline, a package that isn't cached is expected (and would be noisy for every package):
+            if !is_offline() {
+                eprintln!("Something went wrong: {err};");
+            }
             return versions;
         },
         Ok(data) => data,
@@ -142,9 +259,7 @@ pub async fn get_latest_version_for_packagename(
     reason = "More generic than the used code above (which only looks at version info)"
 )]
 pub async fn get_pypi_data_for_packagename(package_name: &PackageName) -> Option<SimpleMetadatum> {
-    let client = SimplePypi::default();
-
-    let data = client.lookup(package_name).await.ok()?;
+    let data = registry().lookup(package_name).await.ok()?;
 
     if let Some((_, metadata)) = data.iter().next_back() {
         if let Some(latest) = metadata.iter().next_back() {
@@ -0,0 +1,386 @@
+use anyhow::{bail, Context};
+use chrono::Utc;
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::ffi::OsStr;
+use std::fs::{File, OpenOptions, TryLockError};
+use std::path::{Path, PathBuf};
+use uv_python::PythonEnvironment;
+
+use crate::metadata::{get_run_dir, load_generic_msgpack, store_generic_msgpack};
+use crate::venv::{activate_venv, create_venv_raw, VenvGuard};
+
+const RUN_METADATA: &str = ".run-metadata";
+/// Before run environments were cached, they lived at `/tmp/uvenv-<package>`.
+const LEGACY_RUN_PREFIX: &str = "uvenv-";
+
+/// Cached run environments older than this (in seconds) are rebuilt instead of reused.
+pub const RUN_ENV_TTL: i64 = 60 * 60 * 24;
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize, Deserialize)]
+pub struct RunMetadata {
+    // order is important, new fields should go last!!
+    pub install_spec: String,
+    pub python: String,
+    pub with: Vec<String>,
+    pub created_at: i64,
+}
+
+impl RunMetadata {
+    pub fn new<S: AsRef<str>>(
+        install_spec: &str,
+        python: Option<&String>,
+        with: &[S],
+    ) -> Self {
+        Self {
+            install_spec: install_spec.to_owned(),
+            python: python.cloned().unwrap_or_default(),
+            with: with.iter().map(|it| it.as_ref().to_owned()).collect(),
+            created_at: Utc::now().timestamp(),
+        }
+    }
+
+    pub fn is_expired(&self) -> bool {
+        Utc::now().timestamp() - self.created_at > RUN_ENV_TTL
+    }
+
+    pub async fn for_dir(dirname: &Path) -> Option<Self> {
+        let mut buf = Vec::new();
+        load_generic_msgpack(&dirname.join(RUN_METADATA), &mut buf)
+            .await
+            .ok()
+    }
+
+    pub async fn save(
+        &self,
+        dirname: &Path,
+    ) -> anyhow::Result<()> {
+        store_generic_msgpack(&dirname.join(RUN_METADATA), self).await
+    }
+}
+
+/// Build a stable name for a run environment, based on everything that influences its contents.
+/// e.g. `httpie-1b9f4a0c2d3e4f5a`
+/// This uses a fixed hash (not `DefaultHasher`), so the keys survive toolchain upgrades.
+pub fn run_env_key<S: AsRef<str>>(
+    name: &str,
+    install_spec: &str,
+    python: Option<&String>,
+    with: &[S],
+) -> String {
+    // the order of --with shouldn't matter for the resulting environment:
+    let with_sorted: Vec<&str> = with.iter().map(AsRef::as_ref).sorted().collect();
+
+    let mut hasher = Sha256::new();
+    for part in [install_spec, python.map_or("", String::as_str)]
+        .into_iter()
+        .chain(with_sorted)
+    {
+        // separator, so e.g. ("ab", "c") and ("a", "bc") don't collide:
+        hasher.update(part);
+        hasher.update([0]);
+    }
+
+    let digest: String = hasher
+        .finalize()
+        .iter()
+        .take(8)
+        .map(|byte| format!("{byte:02x}"))
+        .collect();
+
+    format!("{name}-{digest}")
+}
+
+pub fn run_env_path(key: &str) -> PathBuf {
+    get_run_dir().join(key)
+}
+
+fn run_env_lockfile(key: &str) -> PathBuf {
+    // note: lock files are never removed, otherwise two processes could end up locking different files.
+    get_run_dir().join(format!("{key}.lock"))
+}
+
+fn open_lockfile(key: &str) -> anyhow::Result<File> {
+    std::fs::create_dir_all(get_run_dir())?;
+
+    let path = run_env_lockfile(key);
+    OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(&path)
+        .with_context(|| format!("Could not open lock file {path:?}"))
+}
+
+/// File lock on a cached run environment.
+/// Runs hold a shared lock while they use the environment (also across exec, see `keep_across_exec`),
+/// (re)building or pruning it requires the exclusive lock.
+/// The lock is released when this is dropped (or when the exec'ed process exits).
+pub struct RunEnvLock(File);
+
+impl RunEnvLock {
+    /// Wait for an exclusive lock (e.g. while another process is still building the same environment).
+    pub async fn exclusive(key: &str) -> anyhow::Result<Self> {
+        let file = open_lockfile(key)?;
+
+        let file = tokio::task::spawn_blocking(move || file.lock().map(|()| file)).await??;
+
+        Ok(Self(file))
+    }
+
+    /// Wait for a shared lock (e.g. while another process is still building the same environment).
+    pub async fn shared(key: &str) -> anyhow::Result<Self> {
+        let file = open_lockfile(key)?;
+
+        let file = tokio::task::spawn_blocking(move || file.lock_shared().map(|()| file)).await??;
+
+        Ok(Self(file))
+    }
+
+    /// Is another process using (or building) the environment right now?
+    /// Unlike `try_exclusive`, this doesn't create the lock file, so it's fine for read-only checks.
+    pub fn is_held(key: &str) -> bool {
+        let Ok(file) = File::open(run_env_lockfile(key)) else {
+            // no lock file: it was never locked
+            return false;
+        };
+
+        matches!(file.try_lock(), Err(TryLockError::WouldBlock))
+    }
+
+    /// Trade an exclusive lock for a shared one.
+    /// `flock` can't do this atomically, so the caller must check the environment again afterwards.
+    async fn into_shared(self) -> anyhow::Result<Self> {
+        let file = self.0;
+        file.unlock()?;
+
+        let file = tokio::task::spawn_blocking(move || file.lock_shared().map(|()| file)).await??;
+
+        Ok(Self(file))
+    }
+
+    /// Don't close the lock file on exec, so the replacing process keeps holding the lock.
+    pub fn keep_across_exec(&self) -> anyhow::Result<()> {
+        rustix::io::fcntl_setfd(&self.0, rustix::io::FdFlags::empty())
+            .with_context(|| "Could not keep the run environment locked")?;
+
+        Ok(())
+    }
+
+    /// Get an exclusive lock if no other process is building the environment right now.
+    pub fn try_exclusive(key: &str) -> anyhow::Result<Option<Self>> {
+        let file = open_lockfile(key)?;
+
+        match file.try_lock() {
+            Ok(()) => Ok(Some(Self(file))),
+            Err(TryLockError::WouldBlock) => Ok(None),
+            Err(TryLockError::Error(err)) => Err(err.into()),
+        }
+    }
+}
+
+/// Is there a complete, non-expired environment at `venv_path`?
+pub async fn is_reusable(venv_path: &Path) -> bool {
+    RunMetadata::for_dir(venv_path)
+        .await
+        .is_some_and(|meta| !meta.is_expired())
+}
+
+/// Is there a completely built (but maybe expired) environment at `venv_path`?
+async fn is_complete(venv_path: &Path) -> bool {
+    RunMetadata::for_dir(venv_path).await.is_some()
+}
+
+/// A locked and activated run environment.
+pub struct RunEnv {
+    // fields are dropped in order: remove a half-built venv *before* releasing the lock
+    guard: VenvGuard,
+    lock: RunEnvLock,
+    pub path: PathBuf,
+    pub venv: PythonEnvironment,
+    /// the venv was just (re)created, so packages still have to be installed
+    pub is_new: bool,
+}
+
+impl RunEnv {
+    /// Use an existing environment, while holding a shared `lock` on it.
+    async fn reuse(
+        lock: RunEnvLock,
+        path: PathBuf,
+    ) -> anyhow::Result<Self> {
+        let venv = activate_venv(&path).await?;
+
+        Ok(Self {
+            guard: VenvGuard::disarmed(),
+            lock,
+            path,
+            venv,
+            is_new: false,
+        })
+    }
+
+    /// Lock the cached environment for `key`, (re)creating the venv if it can't be reused.
+    /// A reused environment is locked shared, a new one exclusively until `finish`.
+    pub async fn open(
+        key: &str,
+        python: Option<&String>,
+        refresh: bool,
+    ) -> anyhow::Result<Self> {
+        let path = run_env_path(key);
+
+        if !refresh {
+            // waits if another process is (re)building it right now:
+            let lock = RunEnvLock::shared(key).await?;
+            if is_reusable(&path).await {
+                return Self::reuse(lock, path).await;
+            }
+        }
+
+        // (re)building requires that no other run is using the environment:
+        let lock = if let Some(lock) = RunEnvLock::try_exclusive(key)? {
+            lock
+        } else {
+            if !refresh {
+                // expired, but still in use by another run: keep using it rather than wait for that run to exit.
+                let lock = RunEnvLock::shared(key).await?;
+                if is_complete(&path).await {
+                    return Self::reuse(lock, path).await;
+                }
+            }
+
+            eprintln!("⏳ Waiting for other runs of this environment to finish...");
+            RunEnvLock::exclusive(key).await?
+        };
+
+        // another process could have built it while we were waiting:
+        if !refresh && is_reusable(&path).await {
+            let lock = lock.into_shared().await?;
+            return Self::reuse(lock, path).await;
+        }
+
+        let guard = VenvGuard::new(&path);
+        create_venv_raw(&path, python, true, true).await?;
+        let venv = activate_venv(&path).await?;
+
+        Ok(Self {
+            guard,
+            lock,
+            path,
+            venv,
+            is_new: true,
+        })
+    }
+
+    /// Mark a new environment as complete, so it will be reused (instead of cleaned up).
+    /// Other runs can use it from now on, the environment stays locked (shared) for this run.
+    pub async fn finish(
+        self,
+        metadata: &RunMetadata,
+    ) -> anyhow::Result<Self> {
+        let Self {
+            mut guard,
+            lock,
+            path,
+            venv,
+            is_new,
+        } = self;
+
+        metadata.save(&path).await?;
+        guard.disarm();
+
+        let lock = lock.into_shared().await?;
+        // it could have been pruned in the moment between the exclusive and the shared lock:
+        if !is_complete(&path).await {
+            bail!("The run environment {path:?} was removed while it was being set up, please try again.");
+        }
+
+        Ok(Self {
+            guard,
+            lock,
+            path,
+            venv,
+            is_new,
+        })
+    }
+
+    /// Keep the environment locked while `exec`ed code runs in it,
+    /// so it can't be pruned or rebuilt from under that process.
+    pub fn keep_locked_across_exec(&self) -> anyhow::Result<()> {
+        self.lock.keep_across_exec()
+    }
+}
+
+/// Run environments left behind by crashed or interrupted runs:
+/// legacy `/tmp/uvenv-*` venvs and cached venvs that were never completely built.
+pub fn find_stale_run_envs() -> Vec<PathBuf> {
+    let mut stale = Vec::new();
+
+    if let Ok(tmp_dir) = std::fs::read_dir(std::env::temp_dir()) {
+        stale.extend(tmp_dir.flatten().map(|entry| entry.path()).filter(|path| {
+            let is_legacy_name = path
+                .file_name()
+                .and_then(OsStr::to_str)
+                .is_some_and(|name| name.starts_with(LEGACY_RUN_PREFIX));
+
+            // only actual venvs, not other files that happen to start with 'uvenv-'
+            is_legacy_name && path.join("pyvenv.cfg").exists()
+        }));
+    }
+
+    if let Ok(run_dir) = std::fs::read_dir(get_run_dir()) {
+        for entry in run_dir.flatten() {
+            let path = entry.path();
+            if !path.is_dir() || path.join(RUN_METADATA).exists() {
+                continue;
+            }
+
+            let key = entry.file_name().into_string().unwrap_or_default();
+            // incomplete, but maybe still being built:
+            if !RunEnvLock::is_held(&key) {
+                stale.push(path);
+            }
+        }
+    }
+
+    stale
+}
+
+/// Complete run environments older than `RUN_ENV_TTL`, which `uvenv run` would rebuild anyway.
+pub async fn find_expired_run_envs() -> Vec<PathBuf> {
+    let mut expired = Vec::new();
+
+    let Ok(run_dir) = std::fs::read_dir(get_run_dir()) else {
+        return expired;
+    };
+
+    for entry in run_dir.flatten() {
+        let path = entry.path();
+        if path.is_dir()
+            && RunMetadata::for_dir(&path)
+                .await
+                .is_some_and(|meta| meta.is_expired())
+        {
+            expired.push(path);
+        }
+    }
+
+    expired
+}
+
+/// Remove a stale (or expired) run environment, unless a `uvenv run` has started (re)building it in the meantime.
+pub async fn remove_stale_run_env(venv: &Path) -> anyhow::Result<bool> {
+    let _lock = if venv.parent() == Some(get_run_dir().as_path()) {
+        let key = venv.file_name().and_then(OsStr::to_str).unwrap_or_default();
+
+        let Some(lock) = RunEnvLock::try_exclusive(key)? else {
+            return Ok(false);
+        };
+        Some(lock)
+    } else {
+        None
+    };
+
+    tokio::fs::remove_dir_all(venv).await?;
+    Ok(true)
+}
@@ -0,0 +1,18 @@
+function uvenv
+    set -l subcommand $argv[1]
+    set -l venv_name $argv[2]
+
+    if test "$subcommand" = "activate"
+        if test -z "$venv_name"
+            echo "Error: No virtual environment name provided."
+            return 1
+        else if not test -d "$HOME/.local/uvenv/venvs/$venv_name"
+            echo "Error: Virtual environment '$venv_name' does not exist."
+            return 2
+        else
+            source "$HOME/.local/uvenv/venvs/$venv_name/bin/activate.fish"
+        end
+    else
+        command uvenv $argv
+    end
+end
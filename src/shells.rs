@@ -0,0 +1,272 @@
+use anyhow::bail;
+use clap_complete::Shell;
+use core::iter;
+use owo_colors::OwoColorize;
+use std::borrow::Cow;
+use std::env;
+use std::ffi::OsStr;
+use std::path::{Path, PathBuf};
+
+use crate::metadata::get_home_dir;
+
+/// Shells that `uvenv setup`, `ensurepath`, `activate` and `completions` can integrate with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum SupportedShell {
+    Bash,
+    Zsh,
+    Fish,
+}
+
+impl SupportedShell {
+    pub fn from_name(name: &str) -> Option<Self> {
+        // $SHELL is usually a full path (e.g. /usr/bin/zsh):
+        let name = Path::new(name)
+            .file_name()
+            .and_then(OsStr::to_str)
+            .unwrap_or_default();
+
+        match name {
+            "bash" => Some(Self::Bash),
+            "zsh" => Some(Self::Zsh),
+            "fish" => Some(Self::Fish),
+            _ => None,
+        }
+    }
+
+    pub const fn from_generator(generator: Shell) -> Option<Self> {
+        match generator {
+            Shell::Bash => Some(Self::Bash),
+            Shell::Zsh => Some(Self::Zsh),
+            Shell::Fish => Some(Self::Fish),
+            _ => None,
+        }
+    }
+
+    /// Detect the user's shell based on $SHELL.
+    pub fn detect() -> Option<Self> {
+        Self::from_name(&env::var("SHELL").unwrap_or_default())
+    }
+
+    /// Detect the user's shell, or show a message saying it is unsupported.
+    pub fn detect_or_warn() -> Option<Self> {
+        let detected = Self::detect();
+
+        if detected.is_none() {
+            eprintln!(
+                "Unsupported shell '{}'. Currently, only bash, zsh and fish are supported.",
+                env::var("SHELL").unwrap_or_default().blue()
+            );
+        }
+
+        detected
+    }
+
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Bash => "bash",
+            Self::Zsh => "zsh",
+            Self::Fish => "fish",
+        }
+    }
+
+    pub const fn generator(self) -> Shell {
+        match self {
+            Self::Bash => Shell::Bash,
+            Self::Zsh => Shell::Zsh,
+            Self::Fish => Shell::Fish,
+        }
+    }
+
+    /// The file uvenv writes its shell integration to.
+    pub fn rc_file(self) -> PathBuf {
+        let home = get_home_dir();
+
+        match self {
+            Self::Bash => home.join(".bashrc"),
+            Self::Zsh => env::var("ZDOTDIR")
+                .map_or(home, PathBuf::from)
+                .join(".zshrc"),
+            // fish loads everything in conf.d, so uvenv can have its own file there:
+            Self::Fish => home
+                .join(".config")
+                .join("fish")
+                .join("conf.d")
+                .join("uvenv.fish"),
+        }
+    }
+
+    /// Whether `rc_file` belongs to uvenv alone (so it can be removed when uvenv's block is).
+    pub const fn owns_rc_file(self) -> bool {
+        matches!(self, Self::Fish)
+    }
+
+    /// Line that loads the output of `uvenv --generate=<shell> <subcommand>` into the current shell.
+    /// Calling eval (instead of writing the code to the rc file itself) means updates are available immediately.
+    pub fn eval_line(
+        self,
+        subcommand: &str,
+    ) -> String {
+        let name = self.name();
+        match self {
+            Self::Bash | Self::Zsh => format!(r#"eval "$(uvenv --generate={name} {subcommand})""#),
+            Self::Fish => format!("uvenv --generate={name} {subcommand} | source"),
+        }
+    }
+
+    /// Code that registers uvenv's dynamic completions (see `complete.rs`).
+    pub fn completion_line(self) -> String {
+        let name = self.name();
+        match self {
+            Self::Bash | Self::Zsh => format!("source <(COMPLETE={name} uvenv)"),
+            Self::Fish => format!("COMPLETE={name} uvenv | source"),
+        }
+    }
+
+    /// Code to append `bin_dir` to $PATH.
+    pub fn path_snippet(
+        self,
+        bin_dir: &str,
+    ) -> String {
+        match self {
+            Self::Bash | Self::Zsh => format!("export PATH=\"$PATH:{bin_dir}\""),
+            Self::Fish => format!("set -gx PATH $PATH \"{bin_dir}\""),
+        }
+    }
+
+    /// Shell function that makes `uvenv activate <venv>` work.
+    pub const fn activate_script(self) -> &'static str {
+        match self {
+            Self::Bash => include_str!("shell/activate.sh"),
+            Self::Zsh => include_str!("shell/activate.zsh"),
+            Self::Fish => include_str!("shell/activate.fish"),
+        }
+    }
+}
+
+/// uvenv's lines in an rc file live in a single block between these markers, which is rewritten in place.
+pub const RC_BLOCK_START: &str = "# >>> uvenv >>>";
+pub const RC_BLOCK_END: &str = "# <<< uvenv <<<";
+/// Older versions appended lines to the rc file, each preceded by this comment.
+const LEGACY_RC_COMMENT: &str = "# Added by `uvenv` at ";
+
+/// A line of an rc file without its line ending ('\n' or '\r\n').
+fn strip_eol(line: &str) -> &str {
+    line.strip_suffix('\n')
+        .map_or(line, |line| line.strip_suffix('\r').unwrap_or(line))
+}
+
+fn is_blank(line: &str) -> bool {
+    strip_eol(line).is_empty()
+}
+
+/// Split rc file contents into the lines that don't belong to uvenv (with their original line endings)
+/// and the lines uvenv manages (from the marked block and legacy lines).
+/// The position of the block (if any) is returned too, so it can be rewritten in place.
+/// A block without end marker is an error: rewriting it would drop the rest of the file.
+fn split_rc(content: &str) -> anyhow::Result<(Vec<&str>, Vec<String>, Option<usize>)> {
+    let mut other: Vec<&str> = Vec::new();
+    let mut managed: Vec<String> = Vec::new();
+    let mut block_at = None;
+
+    let mut in_block = false;
+    let mut legacy_line_follows = false;
+
+    for raw_line in content.split_inclusive('\n') {
+        let line = strip_eol(raw_line);
+
+        let is_managed = if in_block {
+            if line == RC_BLOCK_END {
+                in_block = false;
+                continue;
+            }
+            true
+        } else if legacy_line_follows {
+            legacy_line_follows = false;
+            true
+        } else if line == RC_BLOCK_START {
+            in_block = true;
+            block_at.get_or_insert(other.len());
+            continue;
+        } else if line.starts_with(LEGACY_RC_COMMENT) {
+            legacy_line_follows = true;
+            // legacy lines were preceded by an empty line:
+            if other.last().is_some_and(|prev| is_blank(prev)) {
+                other.pop();
+            }
+            continue;
+        } else {
+            false
+        };
+
+        if !is_managed {
+            other.push(raw_line);
+        } else if !line.is_empty() && !managed.iter().any(|known| known == line) {
+            managed.push(line.to_owned());
+        }
+    }
+
+    if in_block {
+        bail!("Found '{RC_BLOCK_START}' without a matching '{RC_BLOCK_END}', please fix this by hand first.");
+    }
+
+    // popping an empty line could have moved the end of `other` before the block:
+    let block_at = block_at.map(|idx: usize| idx.min(other.len()));
+
+    Ok((other, managed, block_at))
+}
+
+/// Lines currently managed by uvenv in an rc file (including legacy ones outside of the block).
+pub fn read_rc_block(content: &str) -> anyhow::Result<Vec<String>> {
+    Ok(split_rc(content)?.1)
+}
+
+/// Replace uvenv's block in an rc file with `lines`, migrating legacy lines into it.
+/// If there was no block yet, it's appended at the end. If `lines` is empty, the block is removed.
+///
+/// Everything outside of the block is kept byte for byte (e.g. CRLF line endings or a missing final newline).
+pub fn upsert_rc_block<S: AsRef<str>>(
+    content: &str,
+    lines: &[S],
+) -> anyhow::Result<String> {
+    let (other, _, block_at) = split_rc(content)?;
+    let mut other: Vec<Cow<'_, str>> = other.into_iter().map(Cow::Borrowed).collect();
+
+    // the block uses the line endings of the rest of the file:
+    let newline = if content.contains("\r\n") {
+        "\r\n"
+    } else {
+        "\n"
+    };
+
+    if !lines.is_empty() {
+        let at = block_at.unwrap_or_else(|| {
+            if let Some(last) = other.last_mut() {
+                if !last.ends_with('\n') {
+                    last.to_mut().push_str(newline);
+                }
+            }
+            // separate a new block from the rest of the file
+            if other.last().is_some_and(|prev| !is_blank(prev)) {
+                other.push(Cow::Borrowed(newline));
+            }
+            other.len()
+        });
+
+        let block = iter::once(RC_BLOCK_START)
+            .chain(lines.iter().map(AsRef::as_ref))
+            .chain(iter::once(RC_BLOCK_END))
+            .map(|line| Cow::Owned(format!("{line}{newline}")));
+
+        other.splice(at..at, block);
+    } else if block_at == Some(other.len()) && other.last().is_some_and(|prev| is_blank(prev)) {
+        // drop the empty line that separated a block at the end from the rest of the file
+        other.pop();
+    }
+
+    Ok(other.concat())
+}
+
+/// Remove uvenv's block (and legacy lines) from an rc file.
+pub fn remove_rc_block(content: &str) -> anyhow::Result<String> {
+    upsert_rc_block::<&str>(content, &[])
+}
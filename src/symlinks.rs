@@ -1,11 +1,15 @@
 use anyhow::{anyhow, bail, Context};
-use std::path::Path;
+use core::fmt::Write;
+use itertools::Itertools;
+use owo_colors::OwoColorize;
+use std::path::{Path, PathBuf};
 
 use uv_pep508::Requirement;
 use uv_python::PythonEnvironment;
 
+use crate::errors::UvenvError;
 use crate::helpers::PathAsStr;
-use crate::metadata::ensure_bin_dir;
+use crate::metadata::{ensure_bin_dir, get_bin_dir, get_venv_dir};
 use configparser::ini::Ini;
 
 pub async fn console_scripts(entry_points_path: &str) -> anyhow::Result<Vec<String>> {
@@ -56,21 +60,14 @@ pub async fn create_symlink(
     symlink: &str,
     venv: &Path,
     force: bool,
-    binaries: &[&str],
 ) -> anyhow::Result<bool> {
     let bin_dir = ensure_bin_dir().await;
 
-    if !binaries.is_empty() && !binaries.contains(&symlink) {
-        return Ok(false);
-    }
-
     let target_path = bin_dir.join(symlink);
 
     if target_path.exists() {
         if !force {
-            bail!(
-                "Script {symlink} already exists in {bin_dir:?}. Use --force to ignore this warning.",
-            )
+            return Err(UvenvError::ScriptConflict(target_path.as_str().to_owned()).into());
         }
 
         tokio::fs::remove_file(&target_path)
@@ -90,6 +87,23 @@ pub async fn create_symlink(
     Ok(true)
 }
 
+/// The first script that would replace an existing file in the bin dir (that isn't already linked to this venv).
+pub async fn find_script_conflict(
+    symlinks: &[String],
+    venv: &Path,
+) -> Option<String> {
+    let bin_dir = ensure_bin_dir().await;
+
+    for symlink in symlinks {
+        let target_path = bin_dir.join(symlink);
+        if target_path.exists() && !check_symlink(symlink, venv).await {
+            return Some(target_path.as_str().to_owned());
+        }
+    }
+
+    None
+}
+
 pub fn is_symlink(symlink_path: &Path) -> bool {
     symlink_path
         .symlink_metadata()
@@ -116,6 +130,33 @@ pub async fn check_symlink(
     is_symlink(&symlink_path) && points_to(&symlink_path, target_path)
 }
 
+/// Symlinks in the bin dir that point into a uvenv venv which doesn't exist (anymore).
+pub fn find_orphaned_symlinks() -> Vec<PathBuf> {
+    let venv_dir = get_venv_dir();
+
+    let Ok(bin_dir) = std::fs::read_dir(get_bin_dir()) else {
+        return Vec::new();
+    };
+
+    bin_dir
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| {
+            let Ok(target) = std::fs::read_link(path) else {
+                // not a symlink
+                return false;
+            };
+
+            // e.g. ~/.local/uvenv/venvs/<venv>/bin/<script> -> <venv>
+            target
+                .strip_prefix(&venv_dir)
+                .ok()
+                .and_then(|relative| relative.components().next())
+                .is_some_and(|venv_name| !venv_dir.join(venv_name).exists())
+        })
+        .collect()
+}
+
 pub async fn remove_symlink(symlink: &str) -> anyhow::Result<()> {
     let bin_dir = ensure_bin_dir().await;
     let target_path = bin_dir.join(symlink);
@@ -134,3 +175,42 @@ pub async fn remove_symlinks(symlinks: &[String]) -> anyhow::Result<()> {
 
     Ok(())
 }
+
+/// Scripts that were added to or removed from the bin directory while syncing a venv.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct ScriptChanges {
+    pub linked: Vec<String>,
+    pub unlinked: Vec<String>,
+}
+
+impl ScriptChanges {
+    pub fn is_empty(&self) -> bool {
+        self.linked.is_empty() && self.unlinked.is_empty()
+    }
+
+    pub fn format_human(&self) -> String {
+        let mut result = String::new();
+
+        if self.is_empty() {
+            return result;
+        }
+
+        if !self.linked.is_empty() {
+            let _ = write!(
+                result,
+                "\n🔗 Linked new scripts: {}",
+                self.linked.iter().map(|script| script.green()).join(", ")
+            );
+        }
+
+        if !self.unlinked.is_empty() {
+            let _ = write!(
+                result,
+                "\n🧹 Removed stale scripts: {}",
+                self.unlinked.iter().map(|script| script.red()).join(", ")
+            );
+        }
+
+        result
+    }
+}
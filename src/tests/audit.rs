@@ -0,0 +1,48 @@
+#[expect(unused_imports, reason = "This is a test file.")]
+use crate::commands::audit::Advisory;
+#[expect(unused_imports, reason = "This is a test file.")]
+use crate::tests::shared::TestResult;
+
+const ADVISORY: &str = r#"{
+  "id": "PYSEC-2023-0001",
+  "aliases": ["CVE-2023-0001"],
+  "affected": [
+    {
+      "package": {"ecosystem": "PyPI", "name": "example"},
+      "ranges": [
+        {"type": "GIT", "events": [{"introduced": "0"}, {"fixed": "abcdef"}]},
+        {
+          "type": "ECOSYSTEM",
+          "events": [
+            {"introduced": "0"},
+            {"fixed": "1.2.3"},
+            {"introduced": "2.0"},
+            {"last_affected": "2.1"},
+            {"introduced": "3.0"}
+          ]
+        }
+      ],
+      "versions": ["0.9.post1"]
+    }
+  ]
+}"#;
+
+#[test]
+#[expect(clippy::panic_in_result_fn, reason = "This is a test file.")]
+fn test_affected_ranges() -> TestResult {
+    let advisory: Advisory = serde_json::from_str(ADVISORY)?;
+    let affected = advisory
+        .affected
+        .first()
+        .expect("Advisory should have an affected package");
+
+    assert_eq!(affected.matches("1.0"), Some(String::from("1.2.3")));
+    assert_eq!(affected.matches("1.2.3"), None);
+    assert_eq!(affected.matches("2.1"), Some(String::new()));
+    assert_eq!(affected.matches("2.2"), None);
+    assert_eq!(affected.matches("3.5"), Some(String::new()));
+    assert_eq!(affected.matches("0.9.post1"), Some(String::from("1.2.3")));
+    assert_eq!(affected.matches("not-a-version"), None);
+
+    Ok(())
+}
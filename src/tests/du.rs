@@ -0,0 +1,21 @@
+#[expect(unused_imports, reason = "This is a test file.")]
+use crate::commands::du::parse_record_line;
+#[expect(unused_imports, reason = "This is a test file.")]
+use crate::tests::shared::TestResult;
+
+#[test]
+#[expect(clippy::panic_in_result_fn, reason = "This is a test file.")]
+fn test_parse_record_line() -> TestResult {
+    assert_eq!(
+        parse_record_line("rich/__init__.py,sha256=abc,6066"),
+        Some(("rich/__init__.py", "sha256=abc"))
+    );
+    assert_eq!(
+        parse_record_line("\"weird,name.py\",sha256=def,12"),
+        Some(("weird,name.py", "sha256=def"))
+    );
+    // RECORD lists itself without a hash:
+    assert_eq!(parse_record_line("rich-13.7.1.dist-info/RECORD,,"), None);
+
+    Ok(())
+}
@@ -0,0 +1,60 @@
+#[expect(unused_imports, reason = "This is a test file.")]
+use crate::errors::{exit_code_for, find_uvenv_error, CommandError, UvenvError};
+#[expect(unused_imports, reason = "This is a test file.")]
+use crate::tests::shared::TestResult;
+
+#[test]
+#[expect(clippy::panic_in_result_fn, reason = "This is a test file.")]
+fn test_classify_stderr() -> TestResult {
+    let resolution = "  × No solution found when resolving dependencies:\n  ╰─▶ Because there is no version of foo==99 ...";
+    assert_eq!(
+        UvenvError::from_stderr("uv pip", resolution),
+        UvenvError::ResolutionFailed(String::from("uv pip"))
+    );
+
+    let network = "error: Failed to fetch: `https://pypi.org/simple/foo/`";
+    assert!(UvenvError::from_stderr("uv pip", network).is_retryable());
+
+    assert_eq!(
+        UvenvError::from_stderr("uv pip", "something else").code(),
+        "command-failed"
+    );
+    assert_eq!(
+        UvenvError::from_stderr(
+            "uv pip",
+            "error: Failed to build `foo`\n  Because of a syntax error ..."
+        )
+        .code(),
+        "command-failed",
+        "Only uv's resolver message means the resolution failed"
+    );
+
+    Ok(())
+}
+
+#[test]
+#[expect(clippy::panic_in_result_fn, reason = "This is a test file.")]
+fn test_exit_code_from_chain() -> TestResult {
+    let plain = anyhow::anyhow!("Something went wrong");
+    assert_eq!(exit_code_for(&plain), 1);
+
+    let interrupted = anyhow::Error::from(UvenvError::Interrupted).context("Something went wrong;");
+    assert_eq!(exit_code_for(&interrupted), 130);
+
+    // stderr stays a separate cause:
+    let failed = anyhow::Error::new(CommandError {
+        exit_code: 2,
+        stderr: String::from("error: boom\n"),
+    })
+    .context(UvenvError::CommandFailed(String::from("uv pip")))
+    .context("Something went wrong trying to install 'foo';");
+
+    assert_eq!(
+        find_uvenv_error(&failed),
+        Some(&UvenvError::CommandFailed(String::from("uv pip")))
+    );
+    assert_eq!(exit_code_for(&failed), 10);
+    assert!(format!("{failed:#}").ends_with("uv pip failed: error: boom"));
+
+    Ok(())
+}
@@ -0,0 +1,59 @@
+#[expect(unused_imports, reason = "This is a test file.")]
+use crate::commands::licenses::{detect_license, parse_metadata_headers};
+#[expect(unused_imports, reason = "This is a test file.")]
+use crate::tests::shared::TestResult;
+
+const METADATA: &str = "Metadata-Version: 2.1
+Name: example
+Version: 1.0
+License: Copyright (c) 2024 Someone
+        Permission is hereby granted, free of charge, ...
+Classifier: Programming Language :: Python :: 3
+Classifier: License :: OSI Approved :: MIT License
+Classifier: License :: OSI Approved :: MIT License
+
+License: this is the long description, not a header
+";
+
+#[test]
+#[expect(clippy::panic_in_result_fn, reason = "This is a test file.")]
+fn test_parse_metadata_headers() -> TestResult {
+    let headers = parse_metadata_headers(METADATA);
+
+    assert_eq!(headers.len(), 7, "The body should not be parsed as headers");
+    assert!(headers.contains(&(
+        String::from("License"),
+        String::from(
+            "Copyright (c) 2024 Someone\nPermission is hereby granted, free of charge, ..."
+        )
+    )));
+
+    Ok(())
+}
+
+#[test]
+#[expect(clippy::panic_in_result_fn, reason = "This is a test file.")]
+fn test_detect_license() -> TestResult {
+    // full license text is skipped in favor of the classifiers:
+    assert_eq!(
+        detect_license(&parse_metadata_headers(METADATA)),
+        "MIT License"
+    );
+
+    let expression = "Name: example\nLicense: MIT\nLicense-Expression: Apache-2.0 OR MIT\n";
+    assert_eq!(
+        detect_license(&parse_metadata_headers(expression)),
+        "Apache-2.0 OR MIT"
+    );
+
+    let license = "Name: example\nLicense: BSD-3-Clause\n";
+    assert_eq!(
+        detect_license(&parse_metadata_headers(license)),
+        "BSD-3-Clause"
+    );
+
+    let unknown = "Name: example\nLicense: UNKNOWN\n";
+    assert_eq!(detect_license(&parse_metadata_headers(unknown)), "UNKNOWN");
+
+    Ok(())
+}
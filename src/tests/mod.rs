@@ -1,4 +1,14 @@
 #![expect(dead_code, reason = "This is a tests module.")]
+mod audit;
+mod du;
+mod errors;
+mod licenses;
+mod pep723;
+mod rc_block;
+mod run_cache;
+mod sbom;
 mod shared;
 mod special_home_dir;
+mod tree;
 mod version;
+mod yanked;
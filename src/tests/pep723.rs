@@ -0,0 +1,62 @@
+#[expect(unused_imports, reason = "This is a test file.")]
+use crate::pep723::{extract_script_block, parse_script_metadata};
+#[expect(unused_imports, reason = "This is a test file.")]
+use crate::tests::shared::TestResult;
+
+const SCRIPT: &str = r#"#!/usr/bin/env python
+# /// script
+# requires-python = ">=3.11"
+# dependencies = [
+#   "requests<3",
+#   "rich",
+# ]
+# ///
+
+import requests
+"#;
+
+#[test]
+#[expect(clippy::panic_in_result_fn, reason = "This is a test file.")]
+fn test_extract_script_block() -> TestResult {
+    let block = extract_script_block(SCRIPT)?.unwrap_or_default();
+
+    assert!(
+        block.starts_with("requires-python"),
+        "Block should start at the first line of metadata"
+    );
+    assert!(
+        !block.contains("///"),
+        "Block markers should not be part of the content"
+    );
+
+    assert_eq!(
+        extract_script_block("import sys\n")?,
+        None,
+        "No block means no metadata"
+    );
+
+    let double = format!("{SCRIPT}\n{SCRIPT}");
+    assert!(
+        extract_script_block(&double).is_err(),
+        "Only one script block is allowed"
+    );
+
+    Ok(())
+}
+
+#[test]
+#[expect(clippy::panic_in_result_fn, reason = "This is a test file.")]
+fn test_parse_script_metadata() -> TestResult {
+    let metadata = parse_script_metadata(SCRIPT)?;
+
+    assert_eq!(metadata.requires_python.as_deref(), Some(">=3.11"));
+    assert_eq!(metadata.dependencies, vec!["requests<3", "rich"]);
+
+    let empty = parse_script_metadata("print('hi')")?;
+    assert!(
+        empty.dependencies.is_empty(),
+        "No block means no dependencies"
+    );
+
+    Ok(())
+}
@@ -0,0 +1,141 @@
+#[expect(unused_imports, reason = "This is a test file.")]
+use crate::shells::{read_rc_block, remove_rc_block, upsert_rc_block};
+#[expect(unused_imports, reason = "This is a test file.")]
+use crate::tests::shared::TestResult;
+
+const BASHRC: &str = "alias ll='ls -l'\n";
+
+const LEGACY_BASHRC: &str = "alias ll='ls -l'
+
+# Added by `uvenv` at 2024-05-01 12:00:00
+export PATH=\"$PATH:/home/user/.local/bin\"
+
+# Added by `uvenv` at 2024-05-01 12:00:00
+export PATH=\"$PATH:/home/user/.local/bin\"
+export EDITOR=vim
+";
+
+#[test]
+#[expect(clippy::panic_in_result_fn, reason = "This is a test file.")]
+fn test_upsert_is_idempotent() -> TestResult {
+    let lines = ["export PATH=\"$PATH:/home/user/.local/bin\""];
+
+    let once = upsert_rc_block(BASHRC, &lines)?;
+    let twice = upsert_rc_block(&once, &lines)?;
+
+    assert_eq!(once, twice, "Rewriting the block should not duplicate it");
+    assert_eq!(
+        once,
+        "alias ll='ls -l'\n\n# >>> uvenv >>>\nexport PATH=\"$PATH:/home/user/.local/bin\"\n# <<< uvenv <<<\n"
+    );
+    assert_eq!(read_rc_block(&once)?, lines);
+
+    Ok(())
+}
+
+#[test]
+#[expect(clippy::panic_in_result_fn, reason = "This is a test file.")]
+fn test_block_is_rewritten_in_place() -> TestResult {
+    let content = "# >>> uvenv >>>\nold\n# <<< uvenv <<<\nalias ll='ls -l'\n";
+
+    let updated = upsert_rc_block(content, &["old", "new"])?;
+
+    assert_eq!(
+        updated,
+        "# >>> uvenv >>>\nold\nnew\n# <<< uvenv <<<\nalias ll='ls -l'\n"
+    );
+
+    Ok(())
+}
+
+#[test]
+#[expect(clippy::panic_in_result_fn, reason = "This is a test file.")]
+fn test_legacy_lines_are_migrated() -> TestResult {
+    let lines = read_rc_block(LEGACY_BASHRC)?;
+    assert_eq!(
+        lines,
+        ["export PATH=\"$PATH:/home/user/.local/bin\""],
+        "Legacy lines should be found (once)"
+    );
+
+    let migrated = upsert_rc_block(LEGACY_BASHRC, &lines)?;
+    assert_eq!(
+        migrated,
+        "alias ll='ls -l'\nexport EDITOR=vim\n\n# >>> uvenv >>>\nexport PATH=\"$PATH:/home/user/.local/bin\"\n# <<< uvenv <<<\n"
+    );
+
+    Ok(())
+}
+
+#[test]
+#[expect(clippy::panic_in_result_fn, reason = "This is a test file.")]
+fn test_remove_block() -> TestResult {
+    let with_block = upsert_rc_block(BASHRC, &["eval \"$(uvenv --generate=bash activate _)\""])?;
+
+    assert_eq!(remove_rc_block(&with_block)?, "alias ll='ls -l'\n");
+    assert_eq!(
+        remove_rc_block(LEGACY_BASHRC)?,
+        "alias ll='ls -l'\nexport EDITOR=vim\n"
+    );
+    assert_eq!(remove_rc_block(BASHRC)?, BASHRC, "Nothing to remove");
+
+    Ok(())
+}
+
+#[test]
+#[expect(clippy::panic_in_result_fn, reason = "This is a test file.")]
+fn test_remove_block_keeps_user_blank_lines() -> TestResult {
+    let content =
+        "alias ll='ls -l'\n\n# >>> uvenv >>>\nold\n# <<< uvenv <<<\n\nexport EDITOR=vim\n\n";
+
+    assert_eq!(
+        remove_rc_block(content)?,
+        "alias ll='ls -l'\n\n\nexport EDITOR=vim\n\n",
+        "Only the separator of a block at the end of the file should be removed"
+    );
+
+    Ok(())
+}
+
+#[test]
+#[expect(clippy::panic_in_result_fn, reason = "This is a test file.")]
+fn test_unterminated_block_is_an_error() -> TestResult {
+    let content = "# >>> uvenv >>>\nold\nalias ll='ls -l'\nexport EDITOR=vim\n";
+
+    assert!(
+        upsert_rc_block(content, &["new"]).is_err(),
+        "The rest of the file should not be treated as part of the block"
+    );
+    assert!(remove_rc_block(content).is_err());
+    assert!(read_rc_block(content).is_err());
+
+    Ok(())
+}
+
+#[test]
+#[expect(clippy::panic_in_result_fn, reason = "This is a test file.")]
+fn test_line_endings_are_kept() -> TestResult {
+    let crlf = "alias ll='ls -l'\r\nexport EDITOR=vim\r\n";
+
+    let with_block = upsert_rc_block(crlf, &["new"])?;
+    assert_eq!(
+        with_block,
+        "alias ll='ls -l'\r\nexport EDITOR=vim\r\n\r\n# >>> uvenv >>>\r\nnew\r\n# <<< uvenv <<<\r\n"
+    );
+    assert_eq!(read_rc_block(&with_block)?, ["new"]);
+    assert_eq!(remove_rc_block(&with_block)?, crlf);
+
+    // no newline at the end of the file:
+    let unterminated = "# >>> uvenv >>>\nold\n# <<< uvenv <<<\nalias ll='ls -l'";
+    assert_eq!(
+        remove_rc_block(unterminated)?,
+        "alias ll='ls -l'",
+        "The missing final newline should not be added"
+    );
+    assert_eq!(
+        upsert_rc_block("alias ll='ls -l'", &["new"])?,
+        "alias ll='ls -l'\n\n# >>> uvenv >>>\nnew\n# <<< uvenv <<<\n"
+    );
+
+    Ok(())
+}
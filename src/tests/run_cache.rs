@@ -0,0 +1,35 @@
+#[expect(unused_imports, reason = "This is a test file.")]
+use crate::run_cache::run_env_key;
+#[expect(unused_imports, reason = "This is a test file.")]
+use crate::tests::shared::TestResult;
+
+#[test]
+#[expect(clippy::panic_in_result_fn, reason = "This is a test file.")]
+fn test_run_env_key_is_stable() -> TestResult {
+    // these keys are directory names on disk, so they may never change between releases:
+    assert_eq!(
+        run_env_key("httpie", "httpie", None, &["rich", "click"]),
+        "httpie-16f00d1775637d7a"
+    );
+    assert_eq!(
+        run_env_key::<&str>("httpie", "httpie", Some(&String::from("3.12")), &[]),
+        "httpie-3d6c4c495f846717"
+    );
+
+    Ok(())
+}
+
+#[test]
+#[expect(clippy::panic_in_result_fn, reason = "This is a test file.")]
+fn test_run_env_key_ignores_with_order() -> TestResult {
+    assert_eq!(
+        run_env_key("httpie", "httpie", None, &["rich", "click"]),
+        run_env_key("httpie", "httpie", None, &["click", "rich"]),
+    );
+    assert_ne!(
+        run_env_key("httpie", "httpie", None, &["rich"]),
+        run_env_key("httpie", "httpie", None, &["rich", "click"]),
+    );
+
+    Ok(())
+}
@@ -0,0 +1,62 @@
+#[expect(unused_imports, reason = "This is a test file.")]
+use crate::commands::sbom::{cyclonedx, spdx, SbomPackage, SbomTool};
+#[expect(unused_imports, reason = "This is a test file.")]
+use crate::tests::shared::TestResult;
+
+fn httpie() -> SbomTool {
+    SbomTool {
+        venv: String::from("httpie"),
+        python_version: String::from("3.12.1"),
+        python_executable: String::from("/usr/bin/python3.12"),
+        packages: vec![
+            SbomPackage {
+                name: String::from("charset-normalizer"),
+                version: String::from("3.3.2"),
+                sha256: None,
+            },
+            SbomPackage {
+                name: String::from("httpie"),
+                version: String::from("3.2.2"),
+                sha256: Some(String::from("abc123")),
+            },
+        ],
+    }
+}
+
+#[test]
+#[expect(clippy::panic_in_result_fn, reason = "This is a test file.")]
+fn test_cyclonedx() -> TestResult {
+    let bom = cyclonedx(&[httpie()], "2024-01-01T00:00:00Z");
+
+    assert_eq!(bom["bomFormat"], "CycloneDX");
+    assert_eq!(bom["components"][0]["purl"], "pkg:pypi/httpie@3.2.2");
+    assert_eq!(bom["components"][0]["hashes"][0]["content"], "abc123");
+    assert_eq!(
+        bom["components"][0]["components"][0]["bom-ref"],
+        "httpie/charset-normalizer"
+    );
+    assert_eq!(bom["components"][0]["components"][1]["version"], "3.12.1");
+    assert_eq!(
+        bom["dependencies"][0]["dependsOn"],
+        serde_json::json!(["httpie/charset-normalizer", "httpie/python"])
+    );
+
+    Ok(())
+}
+
+#[test]
+#[expect(clippy::panic_in_result_fn, reason = "This is a test file.")]
+fn test_spdx() -> TestResult {
+    let doc = spdx(&[httpie()], "2024-01-01T00:00:00Z");
+
+    assert_eq!(doc["spdxVersion"], "SPDX-2.3");
+    assert_eq!(doc["packages"][0]["SPDXID"], "SPDXRef-httpie-httpie");
+    assert_eq!(
+        doc["packages"][2]["externalRefs"][0]["referenceLocator"],
+        "pkg:pypi/charset-normalizer@3.3.2"
+    );
+    assert_eq!(doc["relationships"][0]["relationshipType"], "DESCRIBES");
+    assert_eq!(doc["relationships"].as_array().map(Vec::len), Some(3));
+
+    Ok(())
+}
@@ -0,0 +1,68 @@
+#[expect(unused_imports, reason = "This is a test file.")]
+use crate::commands::tree::root_packages;
+#[expect(unused_imports, reason = "This is a test file.")]
+use crate::tests::shared::TestResult;
+#[expect(unused_imports, reason = "This is a test file.")]
+use core::str::FromStr;
+#[expect(unused_imports, reason = "This is a test file.")]
+use std::collections::{BTreeMap, HashSet};
+#[expect(unused_imports, reason = "This is a test file.")]
+use uv_pep508::PackageName;
+
+/// (transitive) dependencies per package, like `TreeBuilder.seen` without the package itself.
+fn transitive(
+    graph: &[(&str, &[&str])]
+) -> Result<BTreeMap<PackageName, HashSet<PackageName>>, Box<dyn core::error::Error>> {
+    let mut result = BTreeMap::new();
+    for (package, dependencies) in graph {
+        let names = dependencies
+            .iter()
+            .map(|dependency| PackageName::from_str(dependency))
+            .collect::<Result<HashSet<_>, _>>()?;
+        result.insert(PackageName::from_str(package)?, names);
+    }
+    Ok(result)
+}
+
+#[test]
+#[expect(clippy::panic_in_result_fn, reason = "This is a test file.")]
+fn test_root_packages_skips_dependencies_of_injected() -> TestResult {
+    // `uvenv inject httpie rich` also installs rich's dependencies:
+    let graph = transitive(&[
+        ("markdown-it-py", &["mdurl"]),
+        ("mdurl", &[]),
+        ("pygments", &[]),
+        ("rich", &["markdown-it-py", "mdurl", "pygments"]),
+        ("setuptools", &[]),
+    ])?;
+    let unrequired: Vec<PackageName> = graph.keys().cloned().collect();
+
+    let roots = root_packages(&unrequired, |package| {
+        graph.get(package).cloned().unwrap_or_default()
+    });
+
+    assert_eq!(
+        roots,
+        vec![
+            PackageName::from_str("rich")?,
+            PackageName::from_str("setuptools")?
+        ]
+    );
+
+    Ok(())
+}
+
+#[test]
+#[expect(clippy::panic_in_result_fn, reason = "This is a test file.")]
+fn test_root_packages_with_cycle() -> TestResult {
+    let graph = transitive(&[("alpha", &["beta"]), ("beta", &["alpha"])])?;
+    let unrequired: Vec<PackageName> = graph.keys().cloned().collect();
+
+    let roots = root_packages(&unrequired, |package| {
+        graph.get(package).cloned().unwrap_or_default()
+    });
+
+    assert_eq!(roots, vec![PackageName::from_str("alpha")?]);
+
+    Ok(())
+}
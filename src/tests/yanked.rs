@@ -0,0 +1,54 @@
+#[expect(unused_imports, reason = "This is a test file.")]
+use crate::pypi::{release_yank_reason, YankedRelease};
+#[expect(unused_imports, reason = "This is a test file.")]
+use crate::tests::shared::TestResult;
+#[expect(unused_imports, reason = "This is a test file.")]
+use uv_pypi_types::Yanked;
+
+#[test]
+#[expect(clippy::panic_in_result_fn, reason = "This is a test file.")]
+fn test_release_yank_reason() -> TestResult {
+    // no files at all:
+    assert_eq!(release_yank_reason(&[]), None);
+    // not yanked:
+    assert_eq!(
+        release_yank_reason(&[None, Some(Yanked::Bool(false))]),
+        None
+    );
+    // only the wheel was yanked, the sdist is still available:
+    assert_eq!(
+        release_yank_reason(&[None, Some(Yanked::Reason("broken wheel".into()))]),
+        None
+    );
+    // yanked without reason:
+    assert_eq!(
+        release_yank_reason(&[Some(Yanked::Bool(true)), Some(Yanked::Bool(true))]),
+        Some(String::new())
+    );
+    // yanked with reason:
+    assert_eq!(
+        release_yank_reason(&[
+            Some(Yanked::Bool(true)),
+            Some(Yanked::Reason("data loss on upgrade".into()))
+        ]),
+        Some(String::from("data loss on upgrade"))
+    );
+
+    Ok(())
+}
+
+#[test]
+#[expect(clippy::panic_in_result_fn, reason = "This is a test file.")]
+fn test_yanked_release_display() -> TestResult {
+    let mut release = YankedRelease {
+        name: String::from("rich"),
+        version: String::from("13.0.0"),
+        reason: String::new(),
+    };
+    assert_eq!(release.to_string(), "rich==13.0.0");
+
+    release.reason = String::from("broken import");
+    assert_eq!(release.to_string(), "rich==13.0.0 (broken import)");
+
+    Ok(())
+}
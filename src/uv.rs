@@ -6,12 +6,16 @@ use itertools::Itertools;
 use owo_colors::OwoColorize;
 use std::ffi::OsStr;
 use std::path::Path;
-use std::{collections::HashSet, path::PathBuf};
+use std::{
+    collections::{BTreeMap, HashSet},
+    path::PathBuf,
+};
 use uv_cache::Cache;
 use uv_client::{BaseClientBuilder, Connectivity};
 use uv_distribution_types::{InstalledDist, Name};
 use uv_installer::SitePackages;
 use uv_pep508::{PackageName, Requirement};
+use uv_pypi_types::VerbatimParsedUrl;
 use uv_python::{
     EnvironmentPreference, Interpreter, PythonDownloads, PythonEnvironment, PythonInstallation,
     PythonPreference, PythonRequest,
@@ -104,6 +108,13 @@ pub fn environment_from_path(path: &Path) -> anyhow::Result<PythonEnvironment> {
     environment_from_path_str(path.to_str().unwrap_or_default())
 }
 
+/// load the `PythonEnvironment` of a venv directory (without activating it)
+pub fn environment_from_root(root: &Path) -> anyhow::Result<PythonEnvironment> {
+    let cache = uv_cache();
+
+    Ok(PythonEnvironment::from_root(root, &cache)?)
+}
+
 /// try to find a `PythonEnvironment` based on the System python
 pub fn system_environment() -> anyhow::Result<PythonEnvironment> {
     let cache = uv_cache();
@@ -224,6 +235,40 @@ pub fn uv_freeze_environ(python: &PythonEnvironment) -> anyhow::Result<String> {
     Ok(result)
 }
 
+/// An installed distribution with the requirements it declares
+/// (markers are not evaluated yet, since that depends on the requested extras).
+#[derive(Debug, Clone)]
+pub struct InstalledPackage {
+    pub version: String,
+    pub requires_dist: Vec<Requirement<VerbatimParsedUrl>>,
+}
+
+/// All packages in the site-packages of an environment, by name.
+pub fn uv_installed_packages(
+    python: &PythonEnvironment
+) -> anyhow::Result<BTreeMap<PackageName, InstalledPackage>> {
+    let site_packages = SitePackages::from_environment(python)?;
+
+    Ok(site_packages
+        .iter()
+        .map(|installed_dist| {
+            // e.g. egg-info without requires.txt: just no dependencies
+            let requires_dist = installed_dist
+                .metadata()
+                .map(|metadata| metadata.requires_dist)
+                .unwrap_or_default();
+
+            (
+                installed_dist.name().clone(),
+                InstalledPackage {
+                    version: installed_dist.version().to_string(),
+                    requires_dist,
+                },
+            )
+        })
+        .collect())
+}
+
 #[expect(
     dead_code,
     reason = "Required for `uv_freeze` (but that function is currently unused)"
@@ -1,10 +1,11 @@
-use crate::helpers::PathToString;
+use crate::errors::UvenvError;
+use crate::helpers::{PathAsStr, PathToString};
 use crate::metadata::venv_path;
 use crate::pip::parse_requirement;
 use crate::uv::{uv, uv_venv};
-use anyhow::{bail, Context};
-use owo_colors::OwoColorize;
+use anyhow::Context;
 use std::env;
+use std::ffi::OsStr;
 use std::path::{Path, PathBuf};
 use uv_pep508::{PackageName, Requirement};
 
@@ -18,8 +19,11 @@ pub async fn create_venv_raw(
     with_pip: bool,
 ) -> anyhow::Result<()> {
     if !force && venv_path.exists() {
-        bail!("'{}' is already installed.\nUse '{}' to update existing tools or pass '{}' to this command to ignore this message.",
-                    &venv_path.to_str().unwrap_or_default().green(), "uvenv upgrade".green(), "--force".green())
+        let venv_name = venv_path
+            .file_name()
+            .and_then(OsStr::to_str)
+            .unwrap_or_else(|| venv_path.as_str());
+        return Err(UvenvError::AlreadyInstalled(venv_name.to_owned()).into());
     }
 
     let mut args: Vec<&str> = vec!["venv", venv_path.to_str().unwrap_or_default()];
@@ -63,6 +67,21 @@ pub async fn activate_venv(venv: &Path) -> anyhow::Result<PythonEnvironment> {
     uv_venv(None).with_context(|| format!("Could not properly activate venv '{venv_str}'!"))
 }
 
+/// Make this process (and everything it starts) behave like `source <venv>/bin/activate`:
+/// `VIRTUAL_ENV` is already set by `activate_venv`, this puts the venv's bin first on `PATH` and unsets `PYTHONHOME`.
+pub fn export_venv_environ(venv: &PythonEnvironment) {
+    let path = env::var_os("PATH").unwrap_or_default();
+
+    let mut paths = vec![venv.scripts().to_path_buf()];
+    paths.extend(env::split_paths(&path));
+
+    if let Ok(new_path) = env::join_paths(paths) {
+        env::set_var("PATH", new_path);
+    }
+
+    env::remove_var("PYTHONHOME");
+}
+
 /// Find the path to an existing venv for an install spec str.
 #[expect(
     dead_code,
@@ -83,7 +102,7 @@ pub async fn setup_environ_from_requirement(
     let requirement_name = requirement.name.to_string();
     let venv_dir = venv_path(&requirement_name);
     if !venv_dir.exists() {
-        bail!("No virtualenv for '{}'.", install_spec.green(),);
+        return Err(UvenvError::VenvNotFound(install_spec.to_owned()).into());
     }
     let environ = activate_venv(&venv_dir).await?;
     Ok((requirement, environ))
@@ -97,6 +116,36 @@ pub async fn remove_venv(venv: &PathBuf) -> anyhow::Result<()> {
     )
 }
 
+/// Removes a (half-built) venv directory when dropped, unless it was disarmed.
+/// Unlike an explicit cleanup step, this also covers early returns via `?` and cancelled futures.
+/// Not `Clone`: every copy would remove the venv again.
+#[derive(Debug, PartialEq, Eq, Default)]
+pub struct VenvGuard(Option<PathBuf>);
+
+impl VenvGuard {
+    pub fn new(venv: &Path) -> Self {
+        Self(Some(venv.to_path_buf()))
+    }
+
+    pub const fn disarmed() -> Self {
+        Self(None)
+    }
+
+    /// The venv is complete, keep it.
+    pub fn disarm(&mut self) {
+        self.0 = None;
+    }
+}
+
+impl Drop for VenvGuard {
+    fn drop(&mut self) {
+        if let Some(venv) = self.0.take() {
+            // sync because Drop can't be async:
+            let _ = std::fs::remove_dir_all(venv);
+        }
+    }
+}
+
 /// Get the absolute path to a script in a venv.
 pub fn venv_script(
     venv: &PythonEnvironment,